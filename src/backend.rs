@@ -0,0 +1,473 @@
+// Cross-platform fast-copy backend used on targets where the EACopy FFI
+// library (Windows-only) is not available.
+//
+// `bindings::copy_file` dispatches here for non-Windows targets: Linux tries
+// `copy_file_range`, falling back to `sendfile`, falling back to a buffered
+// read/write loop; macOS tries `clonefile`/`fcopyfile` before falling back to
+// the same buffered loop. Both paths reuse the size-based buffer heuristic
+// already used by the Windows path (64 KB / 1 MB / 8 MB by source size).
+//
+// `copy_file_mmap` is an alternate strategy, selected via
+// `Config::copy_strategy`/`Config::mmap_threshold`, that maps both files
+// instead of reading and writing through a buffer; it falls back to
+// `copy_file_fast` if mapping fails.
+
+use std::any::Any;
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+use crate::error::{Error, Result};
+
+/// Pick a buffer size for a buffered copy based on the source file size,
+/// mirroring the heuristic used by the Windows `copyFile` wrapper.
+fn buffer_size_for(len: u64) -> usize {
+    if len < 1024 * 1024 {
+        64 * 1024
+    } else if len < 100 * 1024 * 1024 {
+        1024 * 1024
+    } else {
+        8 * 1024 * 1024
+    }
+}
+
+/// Copy `source` to `dest` using whatever kernel-accelerated path is
+/// available on this platform, falling back to a buffered read/write loop.
+/// Returns the number of bytes copied.
+pub fn copy_file_fast(source: &Path, dest: &Path) -> Result<u64> {
+    let src_file = File::open(source).map_err(Error::Io)?;
+    let metadata = src_file.metadata().map_err(Error::Io)?;
+
+    if !metadata.is_file() {
+        // Pipes, sockets, device nodes etc. don't support the fast paths;
+        // go straight to the buffered loop.
+        let dst_file = File::create(dest).map_err(Error::Io)?;
+        return buffered_copy(src_file, dst_file, buffer_size_for(metadata.len()));
+    }
+
+    #[allow(unused_mut)]
+    let mut dst_file = File::create(dest).map_err(Error::Io)?;
+    // Bytes already moved by a strategy that gave up partway through (its fd
+    // offsets have already advanced that far, so whatever runs next picks up
+    // from the right place) and so must be added to the final byte count.
+    #[allow(unused_mut)]
+    let mut already_copied = 0u64;
+
+    #[cfg(target_os = "linux")]
+    {
+        let (copied, done) = linux::try_copy_file_range(&src_file, &dst_file, metadata.len())?;
+        already_copied += copied;
+        if done {
+            return Ok(already_copied);
+        }
+
+        let (copied, done) = linux::try_sendfile(&src_file, &dst_file, metadata.len())?;
+        already_copied += copied;
+        if done {
+            return Ok(already_copied);
+        }
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        if let Some(copied) = macos::try_clonefile(source, dest, metadata.len())? {
+            return Ok(copied);
+        }
+        // `try_clonefile` unlinks `dest` before attempting the clone (required
+        // by clonefile(2)), so on the ENOTSUP/ENOSYS/EXDEV fallback path
+        // `dst_file` now points at an unlinked inode. Re-create it before
+        // falling through to the buffered loop, or the copy would silently
+        // "succeed" without leaving anything at `dest`.
+        dst_file = File::create(dest).map_err(Error::Io)?;
+    }
+
+    Ok(already_copied + buffered_copy(src_file, dst_file, buffer_size_for(metadata.len()))?)
+}
+
+/// Plain buffered read/write loop used as the universal fallback.
+fn buffered_copy(mut src: File, mut dst: File, buffer_size: usize) -> Result<u64> {
+    let mut buf = vec![0u8; buffer_size];
+    let mut total = 0u64;
+
+    loop {
+        let read = match src.read(&mut buf) {
+            Ok(0) => break,
+            Ok(n) => n,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(Error::Io(e)),
+        };
+
+        dst.write_all(&buf[..read]).map_err(Error::Io)?;
+        total += read as u64;
+    }
+
+    Ok(total)
+}
+
+/// Copy from `reader` to `writer` until EOF, returning the number of bytes
+/// transferred. Modeled on `std::io::copy`, but specialized: when both ends
+/// turn out to be real files (including `BufReader<File>`/`BufWriter<File>`
+/// wrappers), any bytes already sitting in a read buffer are drained with a
+/// single `write` and flushed first, then the rest of the transfer goes
+/// through `copy_file_range` on Linux for regular files, and through a
+/// buffered loop elsewhere or when that syscall is unsupported.
+/// `Interrupted` errors are retried transparently.
+pub fn copy_stream<R, W>(mut reader: R, mut writer: W) -> Result<u64>
+where
+    R: Read + Any,
+    W: Write + Any,
+{
+    let mut drained: u64 = 0;
+
+    // Drain whatever is already buffered in a BufReader before deciding
+    // whether we can take the fast file-to-file path, so those bytes aren't
+    // silently skipped by a later file-offset-based copy.
+    if let Some(buffered) = (&mut reader as &mut dyn Any).downcast_mut::<BufReader<File>>() {
+        let pending = buffered.buffer().to_vec();
+        if !pending.is_empty() {
+            writer.write_all(&pending).map_err(Error::Io)?;
+            drained += pending.len() as u64;
+            buffered.consume(pending.len());
+        }
+    }
+
+    // Push the drained bytes (and anything else a BufWriter holds) out to the
+    // file before the fast path writes to it directly.
+    writer.flush().map_err(Error::Io)?;
+
+    if let (Some(src_file), Some(dst_file)) = (
+        extract_file_ref(&reader),
+        extract_file_mut(&mut writer),
+    ) {
+        let src = src_file.try_clone().map_err(Error::Io)?;
+        let dst = dst_file.try_clone().map_err(Error::Io)?;
+        let metadata = src.metadata().map_err(Error::Io)?;
+        #[cfg(target_os = "linux")]
+        if metadata.is_file() {
+            let (copied, done) = linux::try_copy_file_range(&src, &dst, metadata.len())?;
+            if done {
+                return Ok(drained + copied);
+            }
+            drained += copied;
+        }
+        let copied = buffered_copy(src, dst, buffer_size_for(metadata.len()))?;
+        return Ok(drained + copied);
+    }
+
+    buffered_copy_generic(reader, writer).map(|n| n + drained)
+}
+
+/// Try to view `reader` as a plain `File`, looking through a `BufReader`
+/// wrapper if present.
+fn extract_file_ref<R: Read + Any>(reader: &R) -> Option<&File> {
+    let any = reader as &dyn Any;
+    if let Some(file) = any.downcast_ref::<File>() {
+        return Some(file);
+    }
+    any.downcast_ref::<BufReader<File>>().map(|b| b.get_ref())
+}
+
+/// Try to view `writer` as a plain `File`, looking through a `BufWriter`
+/// wrapper if present.
+fn extract_file_mut<W: Write + Any>(writer: &mut W) -> Option<&File> {
+    let any = writer as &mut dyn Any;
+    if let Some(file) = any.downcast_ref::<File>() {
+        return Some(file);
+    }
+    any.downcast_ref::<BufWriter<File>>().map(|b| b.get_ref())
+}
+
+/// Generic adaptively-buffered loop used when neither end is a real file
+/// (sockets, pipes, in-memory buffers, HTTP bodies, ...).
+fn buffered_copy_generic<R: Read, W: Write>(mut reader: R, mut writer: W) -> Result<u64> {
+    let mut buf = vec![0u8; 64 * 1024];
+    let mut total = 0u64;
+
+    loop {
+        let read = match reader.read(&mut buf) {
+            Ok(0) => break,
+            Ok(n) => n,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(Error::Io(e)),
+        };
+
+        writer.write_all(&buf[..read]).map_err(Error::Io)?;
+        total += read as u64;
+    }
+
+    Ok(total)
+}
+
+/// Copy `source` to `dest` by memory-mapping both ends and copying in one
+/// slice-to-slice operation instead of looping over `read`/`write` calls,
+/// avoiding per-chunk syscall overhead on large files. A zero-length source
+/// (most platforms reject mapping an empty file) just yields an empty
+/// `dest` without mapping anything. Falls back to `copy_file_fast` if
+/// mapping either end fails, e.g. on a filesystem/platform that doesn't
+/// support `mmap` (some FUSE/NFS mounts).
+pub fn copy_file_mmap(source: &Path, dest: &Path) -> Result<u64> {
+    let src_file = File::open(source).map_err(Error::Io)?;
+    let len = src_file.metadata().map_err(Error::Io)?.len();
+
+    if len == 0 {
+        File::create(dest).map_err(Error::Io)?;
+        return Ok(0);
+    }
+
+    match copy_file_mmap_inner(&src_file, dest, len) {
+        Ok(bytes) => Ok(bytes),
+        Err(_) => copy_file_fast(source, dest),
+    }
+}
+
+fn copy_file_mmap_inner(src_file: &File, dest: &Path, len: u64) -> Result<u64> {
+    // Safety: `src_file` is only read for the duration of this mapping and
+    // not concurrently truncated by this process.
+    let src_map = unsafe { memmap2::Mmap::map(src_file).map_err(Error::Io)? };
+
+    let dst_file = File::create(dest).map_err(Error::Io)?;
+    dst_file.set_len(len).map_err(Error::Io)?;
+
+    // Safety: `dst_file` was just created and sized by us above; nothing
+    // else is expected to hold it open concurrently.
+    let mut dst_map = unsafe { memmap2::MmapMut::map_mut(&dst_file).map_err(Error::Io)? };
+
+    dst_map.copy_from_slice(&src_map);
+    dst_map.flush().map_err(Error::Io)?;
+
+    Ok(len)
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use super::Result;
+    use crate::error::Error;
+    use std::fs::File;
+    use std::os::unix::io::AsRawFd;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    // Once either syscall has been observed as unsupported on this system,
+    // skip probing it again on subsequent calls from `batch_copy`.
+    static COPY_FILE_RANGE_UNSUPPORTED: AtomicBool = AtomicBool::new(false);
+    static SENDFILE_UNSUPPORTED: AtomicBool = AtomicBool::new(false);
+
+    /// Try `copy_file_range(2)` in a loop until EOF, restarting on `EINTR`.
+    /// Returns `(bytes_copied, true)` once the whole transfer completes.
+    /// Returns `(bytes_copied, false)` if the syscall turns out to be
+    /// unsupported for this pair of files partway through, so the caller
+    /// falls through to the next strategy; `bytes_copied` still counts, since
+    /// both fds' offsets already advanced that far.
+    pub fn try_copy_file_range(src: &File, dst: &File, len: u64) -> Result<(u64, bool)> {
+        if COPY_FILE_RANGE_UNSUPPORTED.load(Ordering::Relaxed) {
+            return Ok((0, false));
+        }
+
+        let mut remaining = len;
+        let mut total = 0u64;
+
+        while remaining > 0 {
+            let chunk = remaining.min(1024 * 1024 * 1024) as usize;
+            let ret = unsafe {
+                libc::copy_file_range(
+                    src.as_raw_fd(),
+                    std::ptr::null_mut(),
+                    dst.as_raw_fd(),
+                    std::ptr::null_mut(),
+                    chunk,
+                    0,
+                )
+            };
+
+            if ret == -1 {
+                let err = std::io::Error::last_os_error();
+                return match err.raw_os_error() {
+                    Some(libc::EINTR) => continue,
+                    Some(libc::ENOSYS) | Some(libc::EXDEV) | Some(libc::EINVAL) => {
+                        COPY_FILE_RANGE_UNSUPPORTED.store(true, Ordering::Relaxed);
+                        Ok((total, false))
+                    }
+                    _ => Err(Error::Io(err)),
+                };
+            }
+
+            if ret == 0 {
+                // Source exhausted before `len` bytes (e.g. concurrent
+                // truncation); treat as done.
+                break;
+            }
+
+            total += ret as u64;
+            remaining -= ret as u64;
+        }
+
+        Ok((total, true))
+    }
+
+    /// Fall back to `sendfile(2)`, which works across filesystems that
+    /// reject `copy_file_range` (e.g. some FUSE/NFS mounts). Same
+    /// `(bytes_copied, done)` convention as `try_copy_file_range`.
+    pub fn try_sendfile(src: &File, dst: &File, len: u64) -> Result<(u64, bool)> {
+        if SENDFILE_UNSUPPORTED.load(Ordering::Relaxed) {
+            return Ok((0, false));
+        }
+
+        let mut remaining = len;
+        let mut total = 0u64;
+
+        while remaining > 0 {
+            let chunk = remaining.min(0x7ffff000) as usize;
+            let ret = unsafe {
+                libc::sendfile(
+                    dst.as_raw_fd(),
+                    src.as_raw_fd(),
+                    std::ptr::null_mut(),
+                    chunk,
+                )
+            };
+
+            if ret == -1 {
+                let err = std::io::Error::last_os_error();
+                return match err.raw_os_error() {
+                    Some(libc::ENOSYS) | Some(libc::EINVAL) => {
+                        SENDFILE_UNSUPPORTED.store(true, Ordering::Relaxed);
+                        Ok((total, false))
+                    }
+                    _ => Err(Error::Io(err)),
+                };
+            }
+
+            if ret == 0 {
+                break;
+            }
+
+            total += ret as u64;
+            remaining -= ret as u64;
+        }
+
+        Ok((total, true))
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod macos {
+    use super::Result;
+    use crate::error::Error;
+    use std::ffi::CString;
+    use std::path::Path;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    static CLONEFILE_UNSUPPORTED: AtomicBool = AtomicBool::new(false);
+
+    extern "C" {
+        fn clonefile(src: *const libc::c_char, dst: *const libc::c_char, flags: u32) -> i32;
+    }
+
+    /// Try `clonefile(2)` for a copy-on-write reflink clone. `dest` must not
+    /// already exist; the caller is expected to have just created it, so
+    /// remove it first since `clonefile` requires the destination be absent.
+    pub fn try_clonefile(source: &Path, dest: &Path, len: u64) -> Result<Option<u64>> {
+        if CLONEFILE_UNSUPPORTED.load(Ordering::Relaxed) {
+            return Ok(None);
+        }
+
+        let _ = std::fs::remove_file(dest);
+
+        let src_c = CString::new(source.as_os_str().to_string_lossy().as_bytes())
+            .map_err(|e| Error::Encoding(e.to_string()))?;
+        let dst_c = CString::new(dest.as_os_str().to_string_lossy().as_bytes())
+            .map_err(|e| Error::Encoding(e.to_string()))?;
+
+        let ret = unsafe { clonefile(src_c.as_ptr(), dst_c.as_ptr(), 0) };
+
+        if ret == 0 {
+            return Ok(Some(len));
+        }
+
+        let err = std::io::Error::last_os_error();
+        match err.raw_os_error() {
+            Some(libc::ENOTSUP) | Some(libc::ENOSYS) | Some(libc::EXDEV) => {
+                CLONEFILE_UNSUPPORTED.store(true, Ordering::Relaxed);
+                Ok(None)
+            }
+            _ => Err(Error::Io(err)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_copy_file_fast_copies_content() -> Result<()> {
+        let dir = tempdir().map_err(Error::Io)?;
+        let src = dir.path().join("source.bin");
+        std::fs::write(&src, b"the quick brown fox").map_err(Error::Io)?;
+        let dst = dir.path().join("dest.bin");
+
+        let copied = copy_file_fast(&src, &dst)?;
+
+        assert_eq!(copied, 19);
+        assert_eq!(std::fs::read(&dst).map_err(Error::Io)?, b"the quick brown fox");
+        Ok(())
+    }
+
+    #[test]
+    fn test_copy_file_mmap_falls_back_for_empty_file() -> Result<()> {
+        let dir = tempdir().map_err(Error::Io)?;
+        let src = dir.path().join("empty.bin");
+        std::fs::write(&src, b"").map_err(Error::Io)?;
+        let dst = dir.path().join("dest.bin");
+
+        let copied = copy_file_mmap(&src, &dst)?;
+
+        assert_eq!(copied, 0);
+        assert!(dst.exists());
+        Ok(())
+    }
+
+    #[test]
+    fn test_copy_stream_between_files() -> Result<()> {
+        let dir = tempdir().map_err(Error::Io)?;
+        let src_path = dir.path().join("source.txt");
+        std::fs::write(&src_path, b"streamed content").map_err(Error::Io)?;
+        let dst_path = dir.path().join("dest.txt");
+
+        let src_file = File::open(&src_path).map_err(Error::Io)?;
+        let dst_file = File::create(&dst_path).map_err(Error::Io)?;
+        let copied = copy_stream(src_file, dst_file)?;
+
+        assert_eq!(copied, 16);
+        assert_eq!(std::fs::read(&dst_path).map_err(Error::Io)?, b"streamed content");
+        Ok(())
+    }
+
+    #[test]
+    fn test_copy_stream_generic_reader_writer() -> Result<()> {
+        use std::cell::RefCell;
+        use std::rc::Rc;
+
+        struct SharedBuf(Rc<RefCell<Vec<u8>>>);
+        impl Write for SharedBuf {
+            fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+                self.0.borrow_mut().extend_from_slice(buf);
+                Ok(buf.len())
+            }
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let src = Cursor::new(b"in-memory".to_vec());
+        let buf = Rc::new(RefCell::new(Vec::new()));
+        let dst = SharedBuf(buf.clone());
+
+        let copied = copy_stream(src, dst)?;
+
+        assert_eq!(copied, 9);
+        assert_eq!(buf.borrow().as_slice(), b"in-memory");
+        Ok(())
+    }
+}
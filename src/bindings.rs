@@ -1,5 +1,8 @@
 // This module contains the raw FFI bindings to the EACopy C++ library
-// Generated bindings are included from the build script
+// Generated bindings are included from the build script. The EACopy C++
+// library only ships a Windows backend, so everything that touches it is
+// gated behind `#[cfg(windows)]`; other targets use the cross-platform
+// kernel-accelerated paths in `crate::backend` instead.
 
 // Include the generated bindings
 #![allow(non_upper_case_globals)]
@@ -8,14 +11,21 @@
 #![allow(dead_code)]
 #![allow(clippy::all)]
 
-// Include the generated bindings
+#[cfg(windows)]
 include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
 
 // Safe wrappers around the raw FFI bindings
 use std::ffi::{c_void, CStr, CString};
-use std::path::Path;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
 use std::ptr;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+use rayon::prelude::*;
 
+use crate::config::{CopyStrategy, ErrorStrategy, ProgressCallback};
 use crate::error::{Error, Result};
 use crate::utils;
 
@@ -24,8 +34,388 @@ pub const EACOPY_COPY_DATA: u32 = 1;
 pub const EACOPY_COPY_ATTRIBUTES: u32 = 2;
 pub const EACOPY_COPY_TIMESTAMPS: u32 = 4;
 
-// Safe wrapper for copyFile function
+/// Copy `source` to `dest`, preserving metadata when requested.
+///
+/// On Windows this goes through the EACopy FFI `copyFile` entry point. On
+/// every other platform there is no EACopy binary to call into, so this
+/// routes to `crate::backend::copy_file_fast`, which uses
+/// `copy_file_range`/`sendfile`/`clonefile` where available.
 pub fn copy_file(source: &Path, dest: &Path, preserve_metadata: bool) -> Result<u64> {
+    copy_file_tracked(source, dest, preserve_metadata).map(|(bytes, _)| bytes)
+}
+
+/// Like `copy_file`, but also reports whether applying metadata flipped the
+/// destination's Unix executable bit, so callers can tally
+/// `CopyStats::exec_bit_changes` (as jj does for its working-copy checkouts).
+pub fn copy_file_tracked(source: &Path, dest: &Path, preserve_metadata: bool) -> Result<(u64, bool)> {
+    #[cfg(windows)]
+    {
+        let bytes = copy_file_windows(source, dest, preserve_metadata)?;
+        Ok((bytes, false))
+    }
+
+    #[cfg(not(windows))]
+    {
+        let bytes = crate::backend::copy_file_fast(source, dest)?;
+
+        let exec_bit_changed = if preserve_metadata {
+            let metadata = std::fs::metadata(source).map_err(Error::Io)?;
+            apply_metadata(&metadata, dest)?
+        } else {
+            false
+        };
+
+        Ok((bytes, exec_bit_changed))
+    }
+}
+
+/// Like `copy_file`, but picks the byte-transfer strategy explicitly instead
+/// of always going through `crate::backend::copy_file_fast`. `Windows` is
+/// unaffected by `strategy`, since the EACopy FFI `copyFile` entry point has
+/// no mmap-based equivalent; this only changes behavior on other platforms.
+pub fn copy_file_with_strategy(
+    source: &Path,
+    dest: &Path,
+    preserve_metadata: bool,
+    strategy: CopyStrategy,
+    mmap_threshold: u64,
+) -> Result<u64> {
+    copy_file_tracked_with_strategy(source, dest, preserve_metadata, strategy, mmap_threshold)
+        .map(|(bytes, _)| bytes)
+}
+
+/// Like `copy_file_tracked`, but picks the byte-transfer strategy explicitly.
+/// See `copy_file_with_strategy`.
+pub fn copy_file_tracked_with_strategy(
+    source: &Path,
+    dest: &Path,
+    preserve_metadata: bool,
+    strategy: CopyStrategy,
+    mmap_threshold: u64,
+) -> Result<(u64, bool)> {
+    #[cfg(windows)]
+    {
+        let _ = (strategy, mmap_threshold);
+        let bytes = copy_file_windows(source, dest, preserve_metadata)?;
+        Ok((bytes, false))
+    }
+
+    #[cfg(not(windows))]
+    {
+        let use_mmap = match strategy {
+            CopyStrategy::Mmap => {
+                let len = std::fs::metadata(source).map_err(Error::Io)?.len();
+                len >= mmap_threshold
+            }
+            CopyStrategy::Buffered => false,
+        };
+
+        let bytes = if use_mmap {
+            crate::backend::copy_file_mmap(source, dest)?
+        } else {
+            crate::backend::copy_file_fast(source, dest)?
+        };
+
+        let exec_bit_changed = if preserve_metadata {
+            let metadata = std::fs::metadata(source).map_err(Error::Io)?;
+            apply_metadata(&metadata, dest)?
+        } else {
+            false
+        };
+
+        Ok((bytes, exec_bit_changed))
+    }
+}
+
+/// Apply `source_metadata`'s mtime, and on Unix its permission bits, to
+/// `dest` after a data-only copy. Returns whether `dest`'s executable bit
+/// changed as a result.
+#[cfg(not(windows))]
+fn apply_metadata(source_metadata: &std::fs::Metadata, dest: &Path) -> Result<bool> {
+    filetime::set_file_mtime(
+        dest,
+        filetime::FileTime::from_last_modification_time(source_metadata),
+    )
+    .map_err(Error::Io)?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dest_metadata = std::fs::metadata(dest).map_err(Error::Io)?;
+        let was_executable = dest_metadata.permissions().mode() & 0o111 != 0;
+        let will_be_executable = source_metadata.permissions().mode() & 0o111 != 0;
+
+        std::fs::set_permissions(dest, source_metadata.permissions()).map_err(Error::Io)?;
+
+        Ok(was_executable != will_be_executable)
+    }
+
+    #[cfg(not(unix))]
+    {
+        Ok(false)
+    }
+}
+
+/// Stream `source` through an xz (LZMA2) encoder into `dest`, for a local
+/// compressed-archive copy rather than an EACopy/server transfer.
+///
+/// `window_bits` is the log2 of the LZMA dictionary size in bytes, same
+/// convention as `CompressionConfig::window_bits`: rust-installer found
+/// moving from an 8 MB (23) to a 64 MB (26) window materially shrinks large
+/// tarballs at the cost of memory. Returns `(uncompressed_bytes,
+/// compressed_bytes)` so callers can report the ratio achieved.
+pub fn compress_file(
+    source: &Path,
+    dest: &Path,
+    level: u32,
+    window_bits: u32,
+) -> Result<(u64, u64)> {
+    if !(23..=26).contains(&window_bits) {
+        return Err(Error::Configuration(format!(
+            "window_bits must be between 23 (8 MB) and 26 (64 MB), got {}",
+            window_bits
+        )));
+    }
+
+    let mut src = BufReader::new(File::open(source).map_err(Error::Io)?);
+    let dst = File::create(dest).map_err(Error::Io)?;
+
+    let mut lzma_options = xz2::stream::LzmaOptions::new_preset(level)
+        .map_err(|e| Error::Compression(e.to_string()))?;
+    lzma_options.dict_size(1u32 << window_bits);
+    let stream = xz2::stream::Stream::new_stream_encoder(&lzma_options, xz2::stream::Check::Crc64)
+        .map_err(|e| Error::Compression(e.to_string()))?;
+    let mut encoder = xz2::write::XzEncoder::new_stream(BufWriter::new(dst), stream);
+
+    let uncompressed_bytes = std::io::copy(&mut src, &mut encoder).map_err(Error::Io)?;
+    encoder.finish().map_err(Error::Io)?.flush().map_err(Error::Io)?;
+
+    let compressed_bytes = std::fs::metadata(dest).map_err(Error::Io)?.len();
+
+    Ok((uncompressed_bytes, compressed_bytes))
+}
+
+/// Reverse of `compress_file`: decode an xz stream at `source` into `dest`.
+/// Returns the number of (uncompressed) bytes written.
+pub fn decompress_file(source: &Path, dest: &Path) -> Result<u64> {
+    let src = BufReader::new(File::open(source).map_err(Error::Io)?);
+    let dst = File::create(dest).map_err(Error::Io)?;
+
+    let stream = xz2::stream::Stream::new_stream_decoder(u64::MAX, 0)
+        .map_err(|e| Error::Compression(e.to_string()))?;
+    let mut decoder = xz2::read::XzDecoder::new_stream(src, stream);
+    let mut dst = BufWriter::new(dst);
+
+    let bytes = std::io::copy(&mut decoder, &mut dst).map_err(Error::Io)?;
+    dst.flush().map_err(Error::Io)?;
+
+    Ok(bytes)
+}
+
+/// Magic header identifying an archive-upload connection on a server's
+/// companion archive port (`server_port + 1`); `ArchiveListener` drops any
+/// connection that does not start with it.
+const ARCHIVE_MAGIC: [u8; 4] = *b"EATA";
+
+/// Build an xz-compressed tar archive of `source` at `archive_path`, for a
+/// single-stream `copytree_with_server` upload instead of one round trip
+/// per file. Archive entries are rooted at `source` itself (`source/foo`
+/// becomes `foo`). Symlinks are stored as symlinks when `symlinks` is set
+/// and followed (their target's contents copied in) otherwise; mtimes and
+/// permissions are only recorded in entry headers when `preserve_metadata`
+/// is set. Returns the compressed archive's size in bytes.
+pub fn build_tar_archive(
+    source: &Path,
+    archive_path: &Path,
+    compression_level: u32,
+    symlinks: bool,
+    preserve_metadata: bool,
+) -> Result<u64> {
+    let dest = File::create(archive_path).map_err(Error::Io)?;
+
+    let lzma_options = xz2::stream::LzmaOptions::new_preset(compression_level)
+        .map_err(|e| Error::Compression(e.to_string()))?;
+    let stream = xz2::stream::Stream::new_stream_encoder(&lzma_options, xz2::stream::Check::Crc64)
+        .map_err(|e| Error::Compression(e.to_string()))?;
+    let encoder = xz2::write::XzEncoder::new_stream(BufWriter::new(dest), stream);
+
+    let mut builder = tar::Builder::new(encoder);
+    builder.follow_symlinks(!symlinks);
+    builder.mode(if preserve_metadata {
+        tar::HeaderMode::Complete
+    } else {
+        tar::HeaderMode::Deterministic
+    });
+    builder.append_dir_all(".", source).map_err(Error::Io)?;
+
+    let encoder = builder.into_inner().map_err(Error::Io)?;
+    encoder.finish().map_err(Error::Io)?.flush().map_err(Error::Io)?;
+
+    std::fs::metadata(archive_path).map(|m| m.len()).map_err(Error::Io)
+}
+
+/// Reverse of `build_tar_archive`: decode the xz stream at `archive_path`
+/// and unpack its tar entries under `dest_root`, creating it if it doesn't
+/// already exist.
+pub fn extract_tar_archive(archive_path: &Path, dest_root: &Path) -> Result<()> {
+    std::fs::create_dir_all(dest_root).map_err(Error::Io)?;
+
+    let src = BufReader::new(File::open(archive_path).map_err(Error::Io)?);
+    let stream = xz2::stream::Stream::new_stream_decoder(u64::MAX, 0)
+        .map_err(|e| Error::Compression(e.to_string()))?;
+    let decoder = xz2::read::XzDecoder::new_stream(src, stream);
+
+    tar::Archive::new(decoder).unpack(dest_root).map_err(Error::Io)
+}
+
+/// Stream `archive_path` to the archive listener at `server_addr:port` (the
+/// caller passes the server's companion port, `server_port + 1`), prefixed
+/// with `ARCHIVE_MAGIC`, `dest_root` (length-prefixed UTF-8), and the
+/// archive's byte length. Returns once the server acknowledges the upload
+/// with a single `1` byte. A refused connection, a missing acknowledgement,
+/// or an explicit rejection is surfaced as `Error::Network`; a failure while
+/// sending is surfaced as `Error::Io`. `EACopy::copytree_with_server` falls
+/// back to its per-file path on either.
+pub fn send_archive_to_server(
+    archive_path: &Path,
+    dest_root: &str,
+    server_addr: &str,
+    port: u16,
+) -> Result<u64> {
+    use std::io::Read;
+    use std::net::TcpStream;
+
+    let mut stream = TcpStream::connect((server_addr, port)).map_err(|e| {
+        Error::Network(format!(
+            "server {}:{} has no archive listener: {}",
+            server_addr, port, e
+        ))
+    })?;
+
+    let archive_len = std::fs::metadata(archive_path).map_err(Error::Io)?.len();
+    let dest_root_bytes = dest_root.as_bytes();
+
+    stream.write_all(&ARCHIVE_MAGIC).map_err(Error::Io)?;
+    stream
+        .write_all(&(dest_root_bytes.len() as u32).to_be_bytes())
+        .map_err(Error::Io)?;
+    stream.write_all(dest_root_bytes).map_err(Error::Io)?;
+    stream.write_all(&archive_len.to_be_bytes()).map_err(Error::Io)?;
+
+    let mut archive_file = BufReader::new(File::open(archive_path).map_err(Error::Io)?);
+    std::io::copy(&mut archive_file, &mut stream).map_err(Error::Io)?;
+
+    let mut ack = [0u8; 1];
+    stream.read_exact(&mut ack).map_err(|e| {
+        Error::Network(format!(
+            "server {}:{} did not acknowledge the archive upload: {}",
+            server_addr, port, e
+        ))
+    })?;
+
+    if ack[0] != 1 {
+        return Err(Error::Network(format!(
+            "server {}:{} rejected the archive upload",
+            server_addr, port
+        )));
+    }
+
+    Ok(archive_len)
+}
+
+/// Background listener accepting `copytree_with_server` archive uploads on
+/// `port`, run alongside `EACopyServer` on its port `+ 1` so older clients
+/// that only speak the per-file EACopy protocol are unaffected. Each
+/// connection is handled serially: read the `ARCHIVE_MAGIC` header,
+/// destination root, and archive bytes into a temp file, unpack it there,
+/// then ack with a single `1` byte (a malformed header or failed unpack
+/// just drops the connection, which the client treats as rejection).
+pub struct ArchiveListener {
+    port: u16,
+    shutdown: std::sync::Arc<AtomicBool>,
+    handle: Option<std::thread::JoinHandle<()>>,
+}
+
+impl ArchiveListener {
+    pub fn start(port: u16) -> Result<Self> {
+        let listener = std::net::TcpListener::bind(("0.0.0.0", port)).map_err(Error::Io)?;
+        listener.set_nonblocking(true).map_err(Error::Io)?;
+        let shutdown = std::sync::Arc::new(AtomicBool::new(false));
+        let thread_shutdown = shutdown.clone();
+
+        let handle = std::thread::spawn(move || {
+            while !thread_shutdown.load(Ordering::Relaxed) {
+                match listener.accept() {
+                    Ok((stream, _)) => {
+                        let _ = handle_archive_upload(stream);
+                    }
+                    Err(ref e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                        std::thread::sleep(std::time::Duration::from_millis(50));
+                    }
+                    Err(_) => break,
+                }
+            }
+        });
+
+        Ok(ArchiveListener {
+            port,
+            shutdown,
+            handle: Some(handle),
+        })
+    }
+
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+}
+
+impl Drop for ArchiveListener {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::Relaxed);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+fn handle_archive_upload(mut stream: std::net::TcpStream) -> Result<()> {
+    use std::io::Read;
+
+    let mut magic = [0u8; 4];
+    stream.read_exact(&mut magic).map_err(Error::Io)?;
+    if magic != ARCHIVE_MAGIC {
+        return Err(Error::Network("unrecognized archive upload header".to_string()));
+    }
+
+    let mut len_buf = [0u8; 4];
+    stream.read_exact(&mut len_buf).map_err(Error::Io)?;
+    let dest_root_len = u32::from_be_bytes(len_buf) as usize;
+    let mut dest_root_buf = vec![0u8; dest_root_len];
+    stream.read_exact(&mut dest_root_buf).map_err(Error::Io)?;
+    let dest_root = String::from_utf8(dest_root_buf).map_err(|e| Error::Encoding(e.to_string()))?;
+
+    let mut archive_len_buf = [0u8; 8];
+    stream.read_exact(&mut archive_len_buf).map_err(Error::Io)?;
+    let archive_len = u64::from_be_bytes(archive_len_buf);
+
+    let temp_path = std::env::temp_dir().join(format!("eacopy-upload-{}.tar.xz", std::process::id()));
+    {
+        let mut temp_file = File::create(&temp_path).map_err(Error::Io)?;
+        std::io::copy(&mut (&mut stream).take(archive_len), &mut temp_file).map_err(Error::Io)?;
+    }
+
+    let result = extract_tar_archive(&temp_path, Path::new(&dest_root));
+    let _ = std::fs::remove_file(&temp_path);
+    result?;
+
+    stream.write_all(&[1u8]).map_err(Error::Io)?;
+    Ok(())
+}
+
+// Safe wrapper for copyFile function
+#[cfg(windows)]
+fn copy_file_windows(source: &Path, dest: &Path, preserve_metadata: bool) -> Result<u64> {
     let source_wide = utils::path_to_wide_string(source);
     let dest_wide = utils::path_to_wide_string(dest);
 
@@ -91,13 +481,73 @@ pub fn copy_file(source: &Path, dest: &Path, preserve_metadata: bool) -> Result<
     }
 }
 
+/// Options controlling which entries `copy_tree` descends into and copies.
+///
+/// Patterns are matched against the entry's path relative to the copy root,
+/// not the absolute path, so `*.tmp` and `**/node_modules/**` behave the way
+/// users expect regardless of where the tree lives on disk.
+#[cfg(windows)]
+#[derive(Clone, Default)]
+pub struct CopyTreeOptions {
+    pub include: Vec<glob::Pattern>,
+    pub exclude: Vec<glob::Pattern>,
+    pub follow_symlinks: bool,
+    pub max_depth: Option<usize>,
+}
+
+#[cfg(windows)]
+impl CopyTreeOptions {
+    fn is_included(&self, relative: &Path) -> bool {
+        if self.exclude.iter().any(|p| p.matches_path(relative)) {
+            return false;
+        }
+        self.include.is_empty() || self.include.iter().any(|p| p.matches_path(relative))
+    }
+}
+
 // Safe wrapper for copyTree function
+#[cfg(windows)]
 pub fn copy_tree(
     source: &Path,
     dest: &Path,
     symlinks: bool,
     ignore_dangling_symlinks: bool,
     dirs_exist_ok: bool,
+) -> Result<u64> {
+    let options = CopyTreeOptions {
+        follow_symlinks: symlinks,
+        ..Default::default()
+    };
+    let mut visited = std::collections::HashSet::new();
+    copy_tree_filtered(
+        source,
+        dest,
+        source,
+        &options,
+        ignore_dangling_symlinks,
+        dirs_exist_ok,
+        &mut visited,
+        0,
+    )
+}
+
+/// Recursive tree copy honouring `options`'s include/exclude globs and
+/// guarding against symlink cycles.
+///
+/// `visited` tracks the (volume serial, file index) pair EACopy's
+/// `FileInfo` reports for each directory we've already descended into --
+/// the Windows analogue of a `(device, inode)` pair -- so a self-referential
+/// symlink can't recurse forever.
+#[cfg(windows)]
+pub fn copy_tree_filtered(
+    source: &Path,
+    dest: &Path,
+    root: &Path,
+    options: &CopyTreeOptions,
+    ignore_dangling_symlinks: bool,
+    dirs_exist_ok: bool,
+    visited: &mut std::collections::HashSet<(u32, u64)>,
+    depth: usize,
 ) -> Result<u64> {
     let source_wide = utils::path_to_wide_string(source);
     let dest_wide = utils::path_to_wide_string(dest);
@@ -105,6 +555,12 @@ pub fn copy_tree(
     let mut total_bytes: u64 = 0;
     let mut error_code: u32 = 0;
 
+    if let Some(max_depth) = options.max_depth {
+        if depth > max_depth {
+            return Ok(0);
+        }
+    }
+
     unsafe {
         // Create the destination directory if it doesn't exist
         if !dest.exists() {
@@ -139,23 +595,39 @@ pub fn copy_tree(
             if file_name != "." && file_name != ".." {
                 let source_path = source.join(&file_name);
                 let dest_path = dest.join(&file_name);
-
-                // Check if it's a directory
-                if find_data.isDirectory() {
-                    // Recursively copy the directory
-                    let bytes = copy_tree(&source_path, &dest_path, symlinks, ignore_dangling_symlinks, dirs_exist_ok)?;
-                    total_bytes += bytes;
-                } else if find_data.isSymbolicLink() && symlinks {
-                    // Handle symlinks
-                    if let Ok(target) = std::fs::read_link(&source_path) {
-                        if target.exists() || !ignore_dangling_symlinks {
-                            std::os::windows::fs::symlink_file(&target, &dest_path)?;
+                let relative = source_path.strip_prefix(root).unwrap_or(&source_path);
+
+                if options.is_included(relative) {
+                    // Check if it's a directory
+                    if find_data.isDirectory() {
+                        // Guard against symlink cycles: skip any directory
+                        // whose (volume, file index) we've already visited.
+                        let key = (find_data.volumeSerial, find_data.fileIndex);
+                        if visited.insert(key) {
+                            let bytes = copy_tree_filtered(
+                                &source_path,
+                                &dest_path,
+                                root,
+                                options,
+                                ignore_dangling_symlinks,
+                                dirs_exist_ok,
+                                visited,
+                                depth + 1,
+                            )?;
+                            total_bytes += bytes;
                         }
+                    } else if find_data.isSymbolicLink() && options.follow_symlinks {
+                        // Handle symlinks
+                        if let Ok(target) = std::fs::read_link(&source_path) {
+                            if target.exists() || !ignore_dangling_symlinks {
+                                std::os::windows::fs::symlink_file(&target, &dest_path)?;
+                            }
+                        }
+                    } else {
+                        // Copy the file with metadata
+                        let bytes = copy_file(&source_path, &dest_path, true)?;
+                        total_bytes += bytes;
                     }
-                } else {
-                    // Copy the file with metadata
-                    let bytes = copy_file(&source_path, &dest_path, true)?;
-                    total_bytes += bytes;
                 }
             }
 
@@ -170,14 +642,73 @@ pub fn copy_tree(
     }
 }
 
+/// Compression codec usable for `copy_with_server`/`delta_copy` network
+/// transfers.
+#[cfg(windows)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompressionCodec {
+    /// No compression.
+    None,
+    /// EACopy's built-in raw/LZ-style codec.
+    Raw,
+    /// zstd, generally better ratio at a similar or better speed than Raw.
+    Zstd,
+}
+
+/// Codec, level, and sliding-window size for a compressed transfer.
+///
+/// `window_bits` is the log2 of the window size in bytes (e.g. `23` for the
+/// EACopy-documented 8 MB default, `26` for the 64 MB "highly redundant
+/// large-file" setting); larger windows trade memory for ratio.
+#[cfg(windows)]
+#[derive(Debug, Clone, Copy)]
+pub struct CompressionConfig {
+    pub codec: CompressionCodec,
+    pub level: u32,
+    pub window_bits: u32,
+}
+
+#[cfg(windows)]
+impl Default for CompressionConfig {
+    fn default() -> Self {
+        CompressionConfig {
+            codec: CompressionCodec::None,
+            level: 0,
+            window_bits: 23, // 8 MB, matching the previous hard-coded bufferSize
+        }
+    }
+}
+
+#[cfg(windows)]
+impl CompressionConfig {
+    /// Validate that `window_bits` falls within EACopy's supported window
+    /// range (8 MB..64 MB, i.e. a power-of-two size between 2^23 and 2^26).
+    pub fn validate(&self) -> Result<()> {
+        if !(23..=26).contains(&self.window_bits) {
+            return Err(Error::Configuration(format!(
+                "window_bits must be between 23 (8 MB) and 26 (64 MB), got {}",
+                self.window_bits
+            )));
+        }
+        Ok(())
+    }
+
+    fn window_size(&self) -> u32 {
+        1u32 << self.window_bits
+    }
+}
+
 // Safe wrapper for copyWithServer function
+#[cfg(windows)]
 pub fn copy_with_server(
     source: &Path,
     dest: &Path,
     server_addr: &str,
     port: u16,
-    compression_level: u32,
+    compression: CompressionConfig,
 ) -> Result<u64> {
+    compression.validate()?;
+
     let source_wide = utils::path_to_wide_string(source);
     let dest_wide = utils::path_to_wide_string(dest);
 
@@ -187,9 +718,13 @@ pub fn copy_with_server(
     // Create client settings
     let mut settings = unsafe { std::mem::zeroed::<eacopy::ClientSettings>() };
     settings.port = port as u32;
-    settings.compressionLevel = compression_level;
+    settings.compressionLevel = if compression.codec == CompressionCodec::None {
+        0
+    } else {
+        compression.level
+    };
     settings.maxThreads = 8; // Default to 8 threads
-    settings.bufferSize = 8 * 1024 * 1024; // Default to 8MB buffer
+    settings.bufferSize = compression.window_size(); // Sliding window / buffer size
     settings.retryCount = 3; // Default to 3 retries
     settings.retryDelay = 1000; // Default to 1 second delay
     settings.timeout = 30000; // Default to 30 seconds timeout
@@ -318,11 +853,11 @@ pub fn copy_with_server(
                     }
 
                     // Recursively copy directory
-                    let bytes = copy_with_server(&source_file, &dest_file, server_addr, port, compression_level)?;
+                    let bytes = copy_with_server(&source_file, &dest_file, server_addr, port, compression)?;
                     total_bytes += bytes;
                 } else {
                     // Copy file
-                    let bytes = copy_with_server(&source_file, &dest_file, server_addr, port, compression_level)?;
+                    let bytes = copy_with_server(&source_file, &dest_file, server_addr, port, compression)?;
                     total_bytes += bytes;
                 }
             }
@@ -337,16 +872,731 @@ pub fn copy_with_server(
     }
 }
 
+/// Counts accumulated while copying a directory tree (or a batch of them),
+/// so callers can report throughput without parsing the progress callback
+/// stream.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CopyStats {
+    pub files: u64,
+    pub dirs: u64,
+    pub bytes_copied: u64,
+    pub symlinks: u64,
+    pub skipped: u64,
+    /// Number of copies where preserving metadata flipped the destination's
+    /// Unix executable bit relative to what was there before.
+    pub exec_bit_changes: u64,
+    /// Bytes actually written for compressed copies made via
+    /// `compress_file`, so callers can compute the ratio against
+    /// `bytes_copied` (the uncompressed size).
+    pub compressed_bytes: u64,
+}
+
+impl CopyStats {
+    fn merge(&mut self, other: CopyStats) {
+        self.files += other.files;
+        self.dirs += other.dirs;
+        self.bytes_copied += other.bytes_copied;
+        self.symlinks += other.symlinks;
+        self.skipped += other.skipped;
+        self.exec_bit_changes += other.exec_bit_changes;
+        self.compressed_bytes += other.compressed_bytes;
+    }
+}
+
+/// Tunes how `copy_directory`/`copy_directory_filtered` dispatch the
+/// file-copy phase of a tree walk: how many worker threads to use, how to
+/// react to a failed file copy, and where to report progress.
+///
+/// The default (`thread_count: 1`) reproduces the old strictly-serial walk.
+#[derive(Clone)]
+pub struct ParallelOptions {
+    pub thread_count: usize,
+    pub error_strategy: ErrorStrategy,
+    pub progress_callback: ProgressCallback,
+    /// When set, the walk still runs in full (directories are enumerated,
+    /// files are sized and "copied" for stats purposes) but no directory,
+    /// symlink, or file is actually written to `dest`.
+    pub dry_run: bool,
+    /// Files at or above this size (in bytes) are preallocated and split
+    /// into `range_chunk_size` byte-range tasks dispatched across the same
+    /// thread pool, instead of one whole-file task per file. Not applied on
+    /// Windows, where file data moves through the EACopy FFI `copyFile`
+    /// entry point instead of `dispatch_file_jobs`'s own I/O.
+    pub large_file_threshold: u64,
+    /// Size (in bytes) of each byte-range chunk a large file is split into.
+    pub range_chunk_size: u64,
+}
+
+impl Default for ParallelOptions {
+    fn default() -> Self {
+        ParallelOptions {
+            thread_count: 1,
+            error_strategy: ErrorStrategy::default(),
+            progress_callback: None,
+            dry_run: false,
+            large_file_threshold: 64 * 1024 * 1024,
+            range_chunk_size: 8 * 1024 * 1024,
+        }
+    }
+}
+
+/// Cross-platform recursive directory copy built on `std::fs`, used by
+/// `EACopy::copytree` so local copies work the same off Windows as they do
+/// through the EACopy FFI `copy_tree`.
+pub fn copy_directory(
+    source: &Path,
+    dest: &Path,
+    symlinks: bool,
+    ignore_dangling_symlinks: bool,
+    dirs_exist_ok: bool,
+) -> Result<CopyStats> {
+    copy_directory_with_options(
+        source,
+        dest,
+        symlinks,
+        ignore_dangling_symlinks,
+        dirs_exist_ok,
+        &ParallelOptions::default(),
+    )
+}
+
+/// Like `copy_directory`, but drives the file-copy phase across a rayon
+/// thread pool sized to `options.thread_count`, as `fcp` does: the directory
+/// skeleton and symlinks are created while walking (cheap, stays serial),
+/// then every regular-file copy is dispatched as an independent job.
+pub fn copy_directory_with_options(
+    source: &Path,
+    dest: &Path,
+    symlinks: bool,
+    ignore_dangling_symlinks: bool,
+    dirs_exist_ok: bool,
+    options: &ParallelOptions,
+) -> Result<CopyStats> {
+    let mut stats = CopyStats::default();
+    let mut jobs = Vec::new();
+    enumerate_directory(
+        source,
+        dest,
+        symlinks,
+        ignore_dangling_symlinks,
+        dirs_exist_ok,
+        options.dry_run,
+        &mut stats,
+        &mut jobs,
+    )?;
+    dispatch_file_jobs(jobs, &mut stats, options)?;
+    Ok(stats)
+}
+
+/// Walk `source`, creating the directory skeleton and symlinks as they're
+/// found, and collecting every regular-file copy into `jobs` instead of
+/// performing it inline so the caller can dispatch them in parallel. When
+/// `dry_run` is set, every check still runs but directories and symlinks are
+/// not actually created at `dest`.
+fn enumerate_directory(
+    source: &Path,
+    dest: &Path,
+    symlinks: bool,
+    ignore_dangling_symlinks: bool,
+    dirs_exist_ok: bool,
+    dry_run: bool,
+    stats: &mut CopyStats,
+    jobs: &mut Vec<(PathBuf, PathBuf)>,
+) -> Result<()> {
+    if dest.exists() {
+        if !dirs_exist_ok {
+            return Err(Error::DestinationExists(dest.to_path_buf()));
+        }
+    } else if !dry_run {
+        std::fs::create_dir_all(dest)?;
+    }
+    stats.dirs += 1;
+
+    for entry in std::fs::read_dir(source)? {
+        let entry = entry?;
+        let file_type = entry.file_type()?;
+        let source_path = entry.path();
+        let dest_path = dest.join(entry.file_name());
+
+        if file_type.is_symlink() {
+            if symlinks {
+                let target = std::fs::read_link(&source_path)?;
+                if !target.exists() && ignore_dangling_symlinks {
+                    continue;
+                }
+                if !dry_run {
+                    symlink(&target, &dest_path)?;
+                }
+                stats.symlinks += 1;
+            } else {
+                jobs.push((source_path, dest_path));
+            }
+        } else if file_type.is_dir() {
+            enumerate_directory(
+                &source_path,
+                &dest_path,
+                symlinks,
+                ignore_dangling_symlinks,
+                dirs_exist_ok,
+                dry_run,
+                stats,
+                jobs,
+            )?;
+        } else {
+            jobs.push((source_path, dest_path));
+        }
+    }
+
+    Ok(())
+}
+
+/// One worker's share of a file-copy job: either the whole file, dispatched
+/// through `copy_file_tracked` exactly as before, or — for a file at or
+/// above `ParallelOptions::large_file_threshold` — one
+/// `ParallelOptions::range_chunk_size` slice of it, copied by `copy_range`
+/// into a destination `dispatch_file_jobs` preallocated up front.
+enum FileTask {
+    WholeFile {
+        job_index: usize,
+        src: PathBuf,
+        dst: PathBuf,
+    },
+    Range {
+        job_index: usize,
+        src: PathBuf,
+        dst: PathBuf,
+        offset: u64,
+        len: u64,
+    },
+}
+
+/// Copy `len` bytes of `src` starting at `offset` into the same byte range
+/// of `dst`, which must already exist and be at least `offset + len` bytes
+/// long (`dispatch_file_jobs` preallocates it with `File::set_len` before
+/// any range task runs, so concurrent tasks for the same file never race on
+/// creating it). Unlike `copy_file_tracked`, this never preserves metadata —
+/// callers apply it once, after every range task for a file has finished.
+fn copy_range(src: &Path, dst: &Path, offset: u64, len: u64) -> Result<()> {
+    let mut src_file = File::open(src).map_err(Error::Io)?;
+    src_file.seek(SeekFrom::Start(offset)).map_err(Error::Io)?;
+
+    let mut dst_file = std::fs::OpenOptions::new()
+        .write(true)
+        .open(dst)
+        .map_err(Error::Io)?;
+    dst_file.seek(SeekFrom::Start(offset)).map_err(Error::Io)?;
+
+    let mut buf = [0u8; 64 * 1024];
+    let mut remaining = len;
+    while remaining > 0 {
+        let to_read = (buf.len() as u64).min(remaining) as usize;
+        let read = src_file.read(&mut buf[..to_read]).map_err(Error::Io)?;
+        if read == 0 {
+            break;
+        }
+        dst_file.write_all(&buf[..read]).map_err(Error::Io)?;
+        remaining -= read as u64;
+    }
+
+    Ok(())
+}
+
+/// Apply `src`'s metadata to `dst` once every range task for a split file
+/// has finished. Range splitting only ever happens off Windows (see
+/// `dispatch_file_jobs`), so this is never called there.
+#[cfg(not(windows))]
+fn apply_metadata_after_range_copy(src: &Path, dst: &Path) -> Result<bool> {
+    let metadata = std::fs::metadata(src).map_err(Error::Io)?;
+    apply_metadata(&metadata, dst)
+}
+
+#[cfg(windows)]
+fn apply_metadata_after_range_copy(_src: &Path, _dst: &Path) -> Result<bool> {
+    unreachable!("dispatch_file_jobs never splits files into range tasks on Windows")
+}
+
+/// Copy every `(source, dest)` job in `jobs` across a rayon thread pool
+/// sized to `options.thread_count`, tallying bytes copied with an atomic
+/// counter so `options.progress_callback` stays accurate under concurrency.
+/// Files at or above `options.large_file_threshold` are preallocated and
+/// split into `options.range_chunk_size` byte-range tasks so several
+/// workers copy different parts of the same large file concurrently,
+/// instead of one worker per whole file; every other file dispatches as a
+/// single whole-file task exactly as before. Under `ErrorStrategy::Ignore`,
+/// failed jobs are counted in `stats.skipped` and the walk continues;
+/// otherwise the first error observed is returned.
+fn dispatch_file_jobs(
+    jobs: Vec<(PathBuf, PathBuf)>,
+    stats: &mut CopyStats,
+    options: &ParallelOptions,
+) -> Result<()> {
+    if jobs.is_empty() {
+        return Ok(());
+    }
+
+    let sizes: Vec<u64> = jobs
+        .iter()
+        .map(|(src, _)| src.metadata().map(|m| m.len()).unwrap_or(0))
+        .collect();
+    let total_bytes: u64 = sizes.iter().sum();
+    // Kept alongside `tasks` (which may clone a job's paths into several
+    // range tasks) so the finalize step below always has one canonical
+    // `(src, dst)` per job, regardless of how many tasks it was split into.
+    let job_paths: Vec<(PathBuf, PathBuf)> = jobs.clone();
+
+    let mut tasks: Vec<FileTask> = Vec::with_capacity(jobs.len());
+    let mut task_counts = vec![0usize; jobs.len()];
+    let mut job_is_split = vec![false; jobs.len()];
+
+    for (job_index, ((src, dst), size)) in jobs.into_iter().zip(sizes.iter().copied()).enumerate() {
+        #[cfg(not(windows))]
+        let split = !options.dry_run && size > 0 && size >= options.large_file_threshold;
+        #[cfg(windows)]
+        let split = false;
+
+        if split {
+            let file = File::create(&dst).map_err(Error::Io)?;
+            file.set_len(size).map_err(Error::Io)?;
+
+            job_is_split[job_index] = true;
+            let chunk_size = options.range_chunk_size.max(1);
+            let mut offset = 0u64;
+            while offset < size {
+                let len = chunk_size.min(size - offset);
+                tasks.push(FileTask::Range {
+                    job_index,
+                    src: src.clone(),
+                    dst: dst.clone(),
+                    offset,
+                    len,
+                });
+                task_counts[job_index] += 1;
+                offset += len;
+            }
+        } else {
+            tasks.push(FileTask::WholeFile { job_index, src, dst });
+            task_counts[job_index] = 1;
+        }
+    }
+
+    let bytes_done = AtomicU64::new(0);
+    let files_done = AtomicU64::new(0);
+    let skipped = AtomicU64::new(0);
+    let exec_bit_changes = AtomicU64::new(0);
+    let first_error: Mutex<Option<Error>> = Mutex::new(None);
+    // Set when the progress callback returns `false`, so tasks not yet
+    // started are skipped instead of dispatched; a task already in flight
+    // still finishes.
+    let cancelled = AtomicBool::new(false);
+    let remaining_tasks: Vec<AtomicUsize> = task_counts.into_iter().map(AtomicUsize::new).collect();
+    let failed: Vec<AtomicBool> = (0..remaining_tasks.len()).map(|_| AtomicBool::new(false)).collect();
+
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(options.thread_count.max(1))
+        .build()
+        .map_err(|e| Error::Configuration(e.to_string()))?;
+
+    pool.install(|| {
+        tasks.par_iter().for_each(|task| {
+            if cancelled.load(Ordering::Relaxed) {
+                return;
+            }
+
+            let job_index = match task {
+                FileTask::WholeFile { job_index, src, dst } => {
+                    let result = if options.dry_run {
+                        src.metadata().map(|m| (m.len(), false)).map_err(Error::Io)
+                    } else {
+                        copy_file_tracked(src, dst, true)
+                    };
+
+                    match result {
+                        Ok((bytes, exec_bit_changed)) => {
+                            bytes_done.fetch_add(bytes, Ordering::Relaxed);
+                            if exec_bit_changed {
+                                exec_bit_changes.fetch_add(1, Ordering::Relaxed);
+                            }
+                        }
+                        Err(e) => {
+                            failed[*job_index].store(true, Ordering::Release);
+                            if options.error_strategy != ErrorStrategy::Ignore {
+                                let mut first = first_error.lock().unwrap();
+                                if first.is_none() {
+                                    *first = Some(e);
+                                }
+                            }
+                        }
+                    }
+
+                    *job_index
+                }
+                FileTask::Range {
+                    job_index,
+                    src,
+                    dst,
+                    offset,
+                    len,
+                } => {
+                    match copy_range(src, dst, *offset, *len) {
+                        Ok(()) => {
+                            bytes_done.fetch_add(*len, Ordering::Relaxed);
+                        }
+                        Err(e) => {
+                            failed[*job_index].store(true, Ordering::Release);
+                            if options.error_strategy != ErrorStrategy::Ignore {
+                                let mut first = first_error.lock().unwrap();
+                                if first.is_none() {
+                                    *first = Some(e);
+                                }
+                            }
+                        }
+                    }
+
+                    *job_index
+                }
+            };
+
+            // Only the task that observes `remaining_tasks` drop to zero
+            // finalizes the job, so a split file's metadata is applied, and
+            // its completion reported, exactly once.
+            if remaining_tasks[job_index].fetch_sub(1, Ordering::AcqRel) != 1 {
+                return;
+            }
+
+            if failed[job_index].load(Ordering::Acquire) {
+                if options.error_strategy == ErrorStrategy::Ignore {
+                    skipped.fetch_add(1, Ordering::Relaxed);
+                }
+                return;
+            }
+
+            let (src, dst) = &job_paths[job_index];
+
+            if job_is_split[job_index] {
+                match apply_metadata_after_range_copy(src, dst) {
+                    Ok(exec_bit_changed) => {
+                        if exec_bit_changed {
+                            exec_bit_changes.fetch_add(1, Ordering::Relaxed);
+                        }
+                    }
+                    Err(e) => {
+                        if options.error_strategy == ErrorStrategy::Ignore {
+                            skipped.fetch_add(1, Ordering::Relaxed);
+                        } else {
+                            let mut first = first_error.lock().unwrap();
+                            if first.is_none() {
+                                *first = Some(e);
+                            }
+                        }
+                        return;
+                    }
+                }
+            }
+
+            files_done.fetch_add(1, Ordering::Relaxed);
+
+            if let Some(callback) = &options.progress_callback {
+                let done = bytes_done.load(Ordering::Relaxed);
+                if !callback(done, total_bytes, &dst.display().to_string()) {
+                    cancelled.store(true, Ordering::Relaxed);
+                    let mut first = first_error.lock().unwrap();
+                    if first.is_none() {
+                        *first = Some(Error::Cancelled(dst.clone()));
+                    }
+                }
+            }
+        });
+    });
+
+    stats.files += files_done.into_inner();
+    stats.bytes_copied += bytes_done.into_inner();
+    stats.skipped += skipped.into_inner();
+    stats.exec_bit_changes += exec_bit_changes.into_inner();
+
+    if let Some(error) = first_error.into_inner().unwrap() {
+        return Err(error);
+    }
+
+    Ok(())
+}
+
+/// Compiled `include_globs`/`exclude_globs`/filter callback from a `Config`,
+/// used by `copy_directory_filtered` to decide per-entry whether to copy,
+/// skip, or prune a directory's contents during `copytree`.
+pub struct DirectoryFilter {
+    include: Vec<glob::Pattern>,
+    exclude: Vec<(bool, glob::Pattern)>,
+    callback: crate::config::FilterCallback,
+}
+
+impl DirectoryFilter {
+    /// Compile `include_globs`/`exclude_globs` into `glob::Pattern`s. An
+    /// exclude pattern prefixed with `!` re-includes anything a broader,
+    /// earlier exclude pattern matched (last match wins), mirroring
+    /// gitignore-style negation.
+    pub fn new(
+        include_globs: &[String],
+        exclude_globs: &[String],
+        callback: crate::config::FilterCallback,
+    ) -> Result<Self> {
+        let include = include_globs
+            .iter()
+            .map(|pattern| {
+                glob::Pattern::new(pattern).map_err(|e| Error::InvalidArgument(e.to_string()))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let exclude = exclude_globs
+            .iter()
+            .map(|pattern| {
+                let (negated, pattern) = match pattern.strip_prefix('!') {
+                    Some(rest) => (true, rest),
+                    None => (false, pattern.as_str()),
+                };
+                glob::Pattern::new(pattern)
+                    .map(|p| (negated, p))
+                    .map_err(|e| Error::InvalidArgument(e.to_string()))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(DirectoryFilter {
+            include,
+            exclude,
+            callback,
+        })
+    }
+
+    /// Whether this filter has no effect, so callers can skip the relative
+    /// path bookkeeping entirely and fall back to the plain copy path.
+    pub fn is_noop(&self) -> bool {
+        self.include.is_empty() && self.exclude.is_empty() && self.callback.is_none()
+    }
+
+    /// Evaluate the configured filter callback and include/exclude globs
+    /// for one entry. Exposed beyond this module so read-only walks like
+    /// `EACopy::plan` can reuse the exact same decision `copytree` would
+    /// make without duplicating it.
+    pub fn decide(&self, relative: &Path, metadata: &std::fs::Metadata) -> crate::config::FilterDecision {
+        use crate::config::FilterDecision;
+
+        if let Some(callback) = &self.callback {
+            match callback(relative, metadata) {
+                FilterDecision::Copy => {}
+                other => return other,
+            }
+        }
+
+        if !self.include.is_empty() && !self.include.iter().any(|p| p.matches_path(relative)) {
+            return FilterDecision::Skip;
+        }
+
+        let mut excluded = false;
+        for (negated, pattern) in &self.exclude {
+            if pattern.matches_path(relative) {
+                excluded = !negated;
+            }
+        }
+
+        if excluded {
+            FilterDecision::Skip
+        } else {
+            FilterDecision::Copy
+        }
+    }
+}
+
+/// Like `copy_directory`, but consults `filter` for every entry so callers
+/// can drive `copytree` with a per-entry callback and/or gitignore-style
+/// include/exclude globs.
+pub fn copy_directory_filtered(
+    source: &Path,
+    dest: &Path,
+    symlinks: bool,
+    ignore_dangling_symlinks: bool,
+    dirs_exist_ok: bool,
+    filter: &DirectoryFilter,
+) -> Result<CopyStats> {
+    copy_directory_filtered_with_options(
+        source,
+        dest,
+        symlinks,
+        ignore_dangling_symlinks,
+        dirs_exist_ok,
+        filter,
+        &ParallelOptions::default(),
+    )
+}
+
+/// Like `copy_directory_with_options`, but consults `filter` for every entry
+/// so callers can drive `copytree` with a per-entry callback and/or
+/// gitignore-style include/exclude globs while still parallelizing the
+/// file-copy phase across `options.thread_count` threads.
+pub fn copy_directory_filtered_with_options(
+    source: &Path,
+    dest: &Path,
+    symlinks: bool,
+    ignore_dangling_symlinks: bool,
+    dirs_exist_ok: bool,
+    filter: &DirectoryFilter,
+    options: &ParallelOptions,
+) -> Result<CopyStats> {
+    if filter.is_noop() {
+        return copy_directory_with_options(
+            source,
+            dest,
+            symlinks,
+            ignore_dangling_symlinks,
+            dirs_exist_ok,
+            options,
+        );
+    }
+
+    let mut stats = CopyStats::default();
+    let mut jobs = Vec::new();
+    enumerate_directory_filtered(
+        source,
+        source,
+        dest,
+        symlinks,
+        ignore_dangling_symlinks,
+        dirs_exist_ok,
+        options.dry_run,
+        filter,
+        &mut stats,
+        &mut jobs,
+    )?;
+    dispatch_file_jobs(jobs, &mut stats, options)?;
+    Ok(stats)
+}
+
+fn enumerate_directory_filtered(
+    root: &Path,
+    source: &Path,
+    dest: &Path,
+    symlinks: bool,
+    ignore_dangling_symlinks: bool,
+    dirs_exist_ok: bool,
+    dry_run: bool,
+    filter: &DirectoryFilter,
+    stats: &mut CopyStats,
+    jobs: &mut Vec<(PathBuf, PathBuf)>,
+) -> Result<()> {
+    use crate::config::FilterDecision;
+
+    if dest.exists() {
+        if !dirs_exist_ok {
+            return Err(Error::DestinationExists(dest.to_path_buf()));
+        }
+    } else if !dry_run {
+        std::fs::create_dir_all(dest)?;
+    }
+    stats.dirs += 1;
+
+    for entry in std::fs::read_dir(source)? {
+        let entry = entry?;
+        let file_type = entry.file_type()?;
+        let metadata = entry.metadata()?;
+        let source_path = entry.path();
+        let dest_path = dest.join(entry.file_name());
+        let relative = source_path.strip_prefix(root).unwrap_or(&source_path);
+
+        match filter.decide(relative, &metadata) {
+            FilterDecision::SkipSubtree => {
+                stats.skipped += 1;
+                continue;
+            }
+            FilterDecision::Skip => {
+                if file_type.is_dir() {
+                    enumerate_directory_filtered(
+                        root,
+                        &source_path,
+                        &dest_path,
+                        symlinks,
+                        ignore_dangling_symlinks,
+                        true,
+                        dry_run,
+                        filter,
+                        stats,
+                        jobs,
+                    )?;
+                } else {
+                    stats.skipped += 1;
+                }
+                continue;
+            }
+            FilterDecision::Copy => {}
+        }
+
+        if file_type.is_symlink() {
+            if symlinks {
+                let target = std::fs::read_link(&source_path)?;
+                if !target.exists() && ignore_dangling_symlinks {
+                    continue;
+                }
+                if !dry_run {
+                    symlink(&target, &dest_path)?;
+                }
+                stats.symlinks += 1;
+            } else {
+                jobs.push((source_path, dest_path));
+            }
+        } else if file_type.is_dir() {
+            enumerate_directory_filtered(
+                root,
+                &source_path,
+                &dest_path,
+                symlinks,
+                ignore_dangling_symlinks,
+                dirs_exist_ok,
+                dry_run,
+                filter,
+                stats,
+                jobs,
+            )?;
+        } else {
+            jobs.push((source_path, dest_path));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(unix)]
+fn symlink(target: &Path, link: &Path) -> Result<()> {
+    std::os::unix::fs::symlink(target, link).map_err(Error::from)
+}
+
+#[cfg(windows)]
+fn symlink(target: &Path, link: &Path) -> Result<()> {
+    if target.is_dir() {
+        std::os::windows::fs::symlink_dir(target, link).map_err(Error::from)
+    } else {
+        std::os::windows::fs::symlink_file(target, link).map_err(Error::from)
+    }
+}
+
 // Safe wrapper for batch operations
-pub fn batch_copy(file_pairs: &[(&Path, &Path)], preserve_metadata: bool) -> Result<u64> {
-    let mut total_bytes: u64 = 0;
+pub fn batch_copy(
+    file_pairs: &[(&Path, &Path)],
+    preserve_metadata: bool,
+    dry_run: bool,
+) -> Result<CopyStats> {
+    let mut stats = CopyStats::default();
 
     for (src, dst) in file_pairs {
-        let bytes = copy_file(src, dst, preserve_metadata)?;
-        total_bytes += bytes;
+        let (bytes, exec_bit_changed) = if dry_run {
+            (src.metadata().map(|m| m.len())?, false)
+        } else {
+            copy_file_tracked(src, dst, preserve_metadata)?
+        };
+        stats.files += 1;
+        stats.bytes_copied += bytes;
+        if exec_bit_changed {
+            stats.exec_bit_changes += 1;
+        }
     }
 
-    Ok(total_bytes)
+    Ok(stats)
 }
 
 // Safe wrapper for batch directory operations
@@ -355,18 +1605,31 @@ pub fn batch_copy_tree(
     symlinks: bool,
     ignore_dangling_symlinks: bool,
     dirs_exist_ok: bool,
-) -> Result<u64> {
-    let mut total_bytes: u64 = 0;
+    dry_run: bool,
+) -> Result<CopyStats> {
+    let mut stats = CopyStats::default();
+    let options = ParallelOptions {
+        dry_run,
+        ..ParallelOptions::default()
+    };
 
     for (src, dst) in dir_pairs {
-        let bytes = copy_tree(src, dst, symlinks, ignore_dangling_symlinks, dirs_exist_ok)?;
-        total_bytes += bytes;
+        let dir_stats = copy_directory_with_options(
+            src,
+            dst,
+            symlinks,
+            ignore_dangling_symlinks,
+            dirs_exist_ok,
+            &options,
+        )?;
+        stats.merge(dir_stats);
     }
 
-    Ok(total_bytes)
+    Ok(stats)
 }
 
 // Safe wrapper for server management
+#[cfg(windows)]
 pub struct EACopyServer {
     server: *mut eacopy::Server,
     settings: eacopy::ServerSettings,
@@ -464,13 +1727,26 @@ impl Drop for EACopyServer {
     }
 }
 
+/// Result of a `delta_copy` call: bytes transferred plus the compression
+/// ratio actually achieved, so callers can tune `CompressionConfig`
+/// empirically.
+#[cfg(windows)]
+#[derive(Debug, Clone, Copy)]
+pub struct DeltaCopyStats {
+    pub bytes: u64,
+    pub compression_ratio: f64,
+}
+
 // Safe wrapper for delta copy operations
+#[cfg(windows)]
 pub fn delta_copy(
     source: &Path,
     dest: &Path,
     reference: &Path,
-    compression_level: u32,
-) -> Result<u64> {
+    compression: CompressionConfig,
+) -> Result<DeltaCopyStats> {
+    compression.validate()?;
+
     let source_wide = utils::path_to_wide_string(source);
     let dest_wide = utils::path_to_wide_string(dest);
     let reference_wide = utils::path_to_wide_string(reference);
@@ -533,6 +1809,11 @@ pub fn delta_copy(
 
         // Perform delta copy
         let mut copy_context = std::mem::zeroed::<eacopy::NetworkCopyContext>();
+        copy_context.compressionLevel = if compression.codec == CompressionCodec::None {
+            0
+        } else {
+            compression.level
+        };
         let mut io_stats = std::mem::zeroed::<eacopy::IOStats>();
         let mut socket_time: u64 = 0;
         let mut socket_size: u64 = 0;
@@ -558,7 +1839,16 @@ pub fn delta_copy(
             return Err(Error::Io(std::io::Error::last_os_error()));
         }
 
-        Ok(source_size)
+        let compression_ratio = if socket_size > 0 {
+            source_size as f64 / socket_size as f64
+        } else {
+            1.0
+        };
+
+        Ok(DeltaCopyStats {
+            bytes: source_size,
+            compression_ratio,
+        })
     }
 }
 
@@ -578,3 +1868,86 @@ macro_rules! defer {
         };
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_copy_file_tracked_copies_content() -> Result<()> {
+        let dir = tempdir().map_err(Error::Io)?;
+        let src = dir.path().join("source.txt");
+        std::fs::write(&src, b"hello").map_err(Error::Io)?;
+        let dst = dir.path().join("dest.txt");
+
+        let (bytes, _exec_bit_changed) = copy_file_tracked(&src, &dst, true)?;
+
+        assert_eq!(bytes, 5);
+        assert_eq!(std::fs::read(&dst).map_err(Error::Io)?, b"hello");
+        Ok(())
+    }
+
+    #[test]
+    fn test_copy_file_with_strategy_mmap_matches_buffered() -> Result<()> {
+        let dir = tempdir().map_err(Error::Io)?;
+        let src = dir.path().join("source.bin");
+        let body = vec![7u8; 4096];
+        std::fs::write(&src, &body).map_err(Error::Io)?;
+        let dst = dir.path().join("dest.bin");
+
+        let bytes = copy_file_with_strategy(&src, &dst, false, CopyStrategy::Mmap, 1)?;
+
+        assert_eq!(bytes, body.len() as u64);
+        assert_eq!(std::fs::read(&dst).map_err(Error::Io)?, body);
+        Ok(())
+    }
+
+    #[test]
+    fn test_directory_filter_include_and_exclude_globs() -> Result<()> {
+        let filter = DirectoryFilter::new(
+            &["*.txt".to_string()],
+            &["secret/**".to_string()],
+            None,
+        )?;
+
+        // Only the relative path drives the include/exclude decision, so any
+        // real file's metadata works here.
+        let dir = tempdir().map_err(Error::Io)?;
+        let probe = dir.path().join("probe.txt");
+        std::fs::write(&probe, b"x").map_err(Error::Io)?;
+        let metadata = std::fs::metadata(&probe).map_err(Error::Io)?;
+
+        assert_eq!(
+            filter.decide(Path::new("notes.txt"), &metadata),
+            crate::config::FilterDecision::Copy
+        );
+        assert_eq!(
+            filter.decide(Path::new("notes.log"), &metadata),
+            crate::config::FilterDecision::Skip
+        );
+        assert_eq!(
+            filter.decide(Path::new("secret/notes.txt"), &metadata),
+            crate::config::FilterDecision::Skip
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_compress_and_decompress_round_trip() -> Result<()> {
+        let dir = tempdir().map_err(Error::Io)?;
+        let src = dir.path().join("source.txt");
+        let body = "round trip me ".repeat(200);
+        std::fs::write(&src, body.as_bytes()).map_err(Error::Io)?;
+        let archive = dir.path().join("archive.xz");
+        let restored = dir.path().join("restored.txt");
+
+        let (uncompressed, _compressed) = compress_file(&src, &archive, 6, 23)?;
+        assert_eq!(uncompressed, body.len() as u64);
+
+        let bytes = decompress_file(&archive, &restored)?;
+        assert_eq!(bytes, body.len() as u64);
+        assert_eq!(std::fs::read_to_string(&restored).map_err(Error::Io)?, body);
+        Ok(())
+    }
+}
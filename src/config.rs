@@ -1,4 +1,6 @@
 use std::collections::HashMap;
+use std::fs::Metadata;
+use std::path::Path;
 use std::sync::{Arc, Mutex};
 
 /// Error handling strategies
@@ -34,8 +36,70 @@ impl Default for LogLevel {
     }
 }
 
-/// Type for progress callback function
-pub type ProgressCallback = Option<Arc<dyn Fn(u64, u64, &str) + Send + Sync>>;
+/// Type for progress callback function. Called as `(copied_bytes,
+/// total_bytes, path)` after each file finishes copying. Returning `false`
+/// requests cancellation: the in-progress operation stops dispatching new
+/// work and returns `Error::Cancelled` instead of its usual result.
+pub type ProgressCallback = Option<Arc<dyn Fn(u64, u64, &str) -> bool + Send + Sync>>;
+
+/// What to do with a directory entry encountered while walking a tree for
+/// `copytree`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilterDecision {
+    /// Copy this entry.
+    Copy,
+    /// Skip this entry, but keep walking siblings (and, for a directory,
+    /// its contents are still considered individually).
+    Skip,
+    /// Skip this entry and, if it's a directory, never descend into it.
+    SkipSubtree,
+}
+
+/// Per-entry filter callback used by `copytree` to decide whether an entry
+/// should be copied, skipped, or pruned entirely.
+pub type FilterCallback = Option<Arc<dyn Fn(&Path, &Metadata) -> FilterDecision + Send + Sync>>;
+
+/// What `ffi::copy_directory` does with an existing destination file instead
+/// of overwriting it outright, modeled on `install`/`cp --backup`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BackupMode {
+    /// Overwrite the destination directly; no backup is kept.
+    None,
+    /// Rename the existing destination to `<name><suffix>` (e.g. `file~`)
+    /// before overwriting it.
+    Simple { suffix: String },
+    /// Rename the existing destination to `<name>.~N~`, where `N` is one
+    /// higher than the largest existing numbered backup.
+    Numbered,
+}
+
+impl Default for BackupMode {
+    fn default() -> Self {
+        BackupMode::None
+    }
+}
+
+/// How `EACopy::copyfile`/`copy`/`copy2` move a single file's bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CopyStrategy {
+    /// Loop over `read`/`write` (or a platform fast path like
+    /// `copy_file_range`/`clonefile`), as `crate::backend::copy_file_fast`
+    /// already does.
+    Buffered,
+    /// Memory-map the source read-only and the destination as a pre-sized
+    /// writable mapping, then copy in one slice-to-slice operation instead
+    /// of looping over syscalls. Only used for files at or above
+    /// `Config::mmap_threshold`; falls back to `Buffered` if mapping either
+    /// end fails (e.g. a filesystem that doesn't support `mmap`). A
+    /// zero-length file is created empty without mapping.
+    Mmap,
+}
+
+impl Default for CopyStrategy {
+    fn default() -> Self {
+        CopyStrategy::Buffered
+    }
+}
 
 /// Configuration options for EACopy
 #[derive(Debug, Clone)]
@@ -62,6 +126,52 @@ pub struct Config {
     pub dirs_exist_ok: bool,
     /// Function to call to report progress
     pub progress_callback: ProgressCallback,
+    /// Per-entry filter callback invoked during `copytree` to decide
+    /// whether to copy, skip, or prune an entry's subtree.
+    pub filter: FilterCallback,
+    /// Glob patterns an entry's path (relative to the copy root) must match
+    /// to be copied. Empty means "match everything".
+    pub include_globs: Vec<String>,
+    /// Glob patterns that exclude an entry's path (relative to the copy
+    /// root). A pattern prefixed with `!` re-includes something an earlier,
+    /// broader exclude pattern matched; the last matching pattern wins.
+    /// `include_globs` never overrides an exclusion.
+    pub exclude_globs: Vec<String>,
+    /// When set, copy operations perform all validation and walk the full
+    /// source tree but skip every actual filesystem write, reporting what
+    /// *would* have been copied.
+    pub dry_run: bool,
+    /// Log2 of the LZMA dictionary/window size (in bytes) used by
+    /// `EACopy::copy_compressed`, between 23 (8 MB) and 26 (64 MB). Larger
+    /// windows trade memory for ratio on highly redundant large files.
+    pub compression_window_bits: u32,
+    /// Files at or above this size (in bytes), copied by `EACopy::copytree`'s
+    /// parallel engine, are split into byte-range chunks and copied by
+    /// multiple workers instead of as a single task.
+    pub large_file_threshold: u64,
+    /// Size (in bytes) of each byte-range chunk a large file is split into.
+    pub range_chunk_size: u64,
+    /// What to do with an existing destination file instead of overwriting
+    /// it outright. Read only by `ffi::copy_directory`; `EACopy::copytree`
+    /// does not consult it.
+    pub backup_mode: BackupMode,
+    /// Whether `ffi::copy_directory` carries over each copied file's
+    /// modification time from the source. `EACopy::copytree` does not
+    /// consult it.
+    pub preserve_mtime: bool,
+    /// Whether `ffi::copy_directory` carries over each copied file's
+    /// permission bits from the source. `EACopy::copytree` does not
+    /// consult it.
+    pub preserve_permissions: bool,
+    /// Whether `ffi::copy_directory` carries over each copied file's owning
+    /// user and group from the source. Unix only; ignored elsewhere.
+    /// `EACopy::copytree` does not consult it.
+    pub preserve_owner: bool,
+    /// How `copyfile`/`copy`/`copy2` move a single file's bytes.
+    pub copy_strategy: CopyStrategy,
+    /// Files at or above this size (in bytes) use `CopyStrategy::Mmap`
+    /// instead of `CopyStrategy::Buffered`, when `copy_strategy` is `Mmap`.
+    pub mmap_threshold: u64,
     /// Advanced options
     pub extra_options: HashMap<String, String>,
 }
@@ -80,6 +190,19 @@ impl Default for Config {
             follow_symlinks: false,
             dirs_exist_ok: false,
             progress_callback: None,
+            filter: None,
+            include_globs: Vec::new(),
+            exclude_globs: Vec::new(),
+            dry_run: false,
+            compression_window_bits: 23, // 8 MB, matching CompressionConfig's default
+            large_file_threshold: 64 * 1024 * 1024,
+            range_chunk_size: 8 * 1024 * 1024,
+            backup_mode: BackupMode::default(),
+            preserve_mtime: true,
+            preserve_permissions: true,
+            preserve_owner: false,
+            copy_strategy: CopyStrategy::default(),
+            mmap_threshold: 64 * 1024 * 1024,
             extra_options: HashMap::new(),
         }
     }
@@ -154,11 +277,102 @@ impl Config {
     /// Set the progress callback function
     pub fn with_progress_callback<F>(mut self, callback: F) -> Self
     where
-        F: Fn(u64, u64, &str) + Send + Sync + 'static,
+        F: Fn(u64, u64, &str) -> bool + Send + Sync + 'static,
     {
         self.progress_callback = Some(Arc::new(callback));
         self
     }
+
+    /// Set a per-entry filter callback, used by `copytree` to decide
+    /// whether to copy, skip, or prune an entry's subtree.
+    pub fn with_filter<F>(mut self, filter: F) -> Self
+    where
+        F: Fn(&Path, &Metadata) -> FilterDecision + Send + Sync + 'static,
+    {
+        self.filter = Some(Arc::new(filter));
+        self
+    }
+
+    /// Set the glob patterns an entry must match to be copied
+    pub fn with_include_globs(mut self, globs: Vec<String>) -> Self {
+        self.include_globs = globs;
+        self
+    }
+
+    /// Set the glob patterns that exclude entries from being copied
+    pub fn with_exclude_globs(mut self, globs: Vec<String>) -> Self {
+        self.exclude_globs = globs;
+        self
+    }
+
+    /// Set whether copy operations only plan their work instead of touching
+    /// the filesystem
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Set the LZMA dictionary/window size (log2 of bytes, 23..=26) used by
+    /// `EACopy::copy_compressed`
+    pub fn with_compression_window(mut self, window_bits: u32) -> Self {
+        self.compression_window_bits = window_bits;
+        self
+    }
+
+    /// Set the size threshold (in bytes) above which `EACopy::copytree`'s
+    /// parallel engine splits a file into byte-range chunks
+    pub fn with_large_file_threshold(mut self, large_file_threshold: u64) -> Self {
+        self.large_file_threshold = large_file_threshold;
+        self
+    }
+
+    /// Set the size (in bytes) of each byte-range chunk a large file is
+    /// split into
+    pub fn with_range_chunk_size(mut self, range_chunk_size: u64) -> Self {
+        self.range_chunk_size = range_chunk_size;
+        self
+    }
+
+    /// Set what `ffi::copy_directory` does with an existing destination file
+    /// instead of overwriting it outright
+    pub fn with_backup_mode(mut self, backup_mode: BackupMode) -> Self {
+        self.backup_mode = backup_mode;
+        self
+    }
+
+    /// Set whether `ffi::copy_directory` preserves each copied file's
+    /// modification time
+    pub fn with_preserve_mtime(mut self, preserve_mtime: bool) -> Self {
+        self.preserve_mtime = preserve_mtime;
+        self
+    }
+
+    /// Set whether `ffi::copy_directory` preserves each copied file's
+    /// permission bits
+    pub fn with_preserve_permissions(mut self, preserve_permissions: bool) -> Self {
+        self.preserve_permissions = preserve_permissions;
+        self
+    }
+
+    /// Set whether `ffi::copy_directory` preserves each copied file's owning
+    /// user and group (Unix only)
+    pub fn with_preserve_owner(mut self, preserve_owner: bool) -> Self {
+        self.preserve_owner = preserve_owner;
+        self
+    }
+
+    /// Set how `copyfile`/`copy`/`copy2` move a single file's bytes
+    pub fn with_copy_strategy(mut self, copy_strategy: CopyStrategy) -> Self {
+        self.copy_strategy = copy_strategy;
+        self
+    }
+
+    /// Set the size threshold (in bytes) above which `CopyStrategy::Mmap`
+    /// maps a file instead of using the buffered path
+    pub fn with_mmap_threshold(mut self, mmap_threshold: u64) -> Self {
+        self.mmap_threshold = mmap_threshold;
+        self
+    }
 }
 
 /// Global configuration instance
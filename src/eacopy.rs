@@ -1,112 +0,0 @@
-// EACopy implementation
-use std::path::Path;
-use crate::error::{Error, Result};
-use crate::config::Config;
-
-/// Main EACopy struct for file operations
-pub struct EACopy {
-    config: Config,
-}
-
-impl EACopy {
-    /// Create a new EACopy instance with default configuration
-    pub fn new() -> Self {
-        Self {
-            config: Config::default(),
-        }
-    }
-
-    /// Create a new EACopy instance with custom configuration
-    pub fn with_config(config: Config) -> Self {
-        Self { config }
-    }
-
-    /// Copy a file from source to destination
-    pub fn copy<P: AsRef<Path>, Q: AsRef<Path>>(&self, source: P, destination: Q) -> Result<()> {
-        // Placeholder implementation
-        println!("Copying file from {:?} to {:?}", source.as_ref(), destination.as_ref());
-        Ok(())
-    }
-
-    /// Copy a directory from source to destination
-    pub fn copy_tree<P: AsRef<Path>, Q: AsRef<Path>>(
-        &self,
-        source: P,
-        destination: Q,
-        recursive: bool,
-        overwrite: bool,
-        preserve_attrs: bool,
-    ) -> Result<()> {
-        // Placeholder implementation
-        println!(
-            "Copying directory from {:?} to {:?}, recursive: {}, overwrite: {}, preserve_attrs: {}",
-            source.as_ref(), destination.as_ref(), recursive, overwrite, preserve_attrs
-        );
-        Ok(())
-    }
-
-    /// Copy with a remote server
-    pub fn copy_with_server<P: AsRef<Path>, Q: AsRef<Path>>(
-        &self,
-        source: P,
-        destination: Q,
-        server: &str,
-    ) -> Result<()> {
-        // Placeholder implementation
-        println!(
-            "Copying with server from {:?} to {:?}, server: {}",
-            source.as_ref(), destination.as_ref(), server
-        );
-        Ok(())
-    }
-}
-
-/// Copy a file from source to destination
-pub fn copy<P: AsRef<Path>, Q: AsRef<Path>>(source: P, destination: Q) -> Result<()> {
-    let eacopy = EACopy::new();
-    eacopy.copy(source, destination)
-}
-
-/// Copy a file from source to destination with custom configuration
-pub fn copy2<P: AsRef<Path>, Q: AsRef<Path>>(source: P, destination: Q, config: Config) -> Result<()> {
-    let eacopy = EACopy::with_config(config);
-    eacopy.copy(source, destination)
-}
-
-/// Copy a file from source to destination (alias for copy)
-pub fn copyfile<P: AsRef<Path>, Q: AsRef<Path>>(source: P, destination: Q) -> Result<()> {
-    copy(source, destination)
-}
-
-/// Copy a directory from source to destination
-pub fn copytree<P: AsRef<Path>, Q: AsRef<Path>>(
-    source: P,
-    destination: Q,
-    recursive: bool,
-    overwrite: bool,
-    preserve_attrs: bool,
-) -> Result<()> {
-    let eacopy = EACopy::new();
-    eacopy.copy_tree(source, destination, recursive, overwrite, preserve_attrs)
-}
-
-/// Copy with a remote server
-pub fn copy_with_server<P: AsRef<Path>, Q: AsRef<Path>>(
-    source: P,
-    destination: Q,
-    server: &str,
-) -> Result<()> {
-    let eacopy = EACopy::new();
-    eacopy.copy_with_server(source, destination, server)
-}
-
-/// Batch copy multiple files
-pub fn batch_copy<P: AsRef<Path>, Q: AsRef<Path>>(file_pairs: &[(P, Q)]) -> Result<()> {
-    let eacopy = EACopy::new();
-    
-    for (source, destination) in file_pairs {
-        eacopy.copy(source, destination)?;
-    }
-    
-    Ok(())
-}
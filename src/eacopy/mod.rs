@@ -1,6 +1,7 @@
 // Main EACopy implementation module
 // This module provides the high-level API for EACopy operations
 
+use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 
@@ -10,7 +11,74 @@ use crate::error::{Error, Result};
 
 // Re-export types
 pub use crate::config::{Config, ErrorStrategy, LogLevel};
-pub use crate::bindings::EACopyServer;
+pub use crate::bindings::{CopyStats, EACopyServer};
+
+/// Whether `path` looks like a glob pattern rather than a literal path.
+fn is_glob_pattern(path: &Path) -> bool {
+    path.to_string_lossy()
+        .chars()
+        .any(|c| c == '*' || c == '?' || c == '[')
+}
+
+/// Expand `pattern` against the filesystem, returning every matching
+/// regular file paired with its destination under `dst`. If `dst` is an
+/// existing directory each match is copied to `dst/<file_name>`; otherwise
+/// the pattern must match exactly one file.
+fn expand_glob_pair(pattern: &str, dst: &Path) -> Result<Vec<(PathBuf, PathBuf)>> {
+    let matches: Vec<PathBuf> = glob::glob(pattern)
+        .map_err(|e| Error::InvalidArgument(format!("Invalid glob pattern {}: {}", pattern, e)))?
+        .filter_map(|entry| entry.ok())
+        .filter(|path| path.is_file())
+        .collect();
+
+    if matches.is_empty() {
+        return Err(Error::InvalidArgument(format!(
+            "Glob pattern matched nothing: {}",
+            pattern
+        )));
+    }
+
+    if matches.len() > 1 && !dst.is_dir() {
+        return Err(Error::InvalidArgument(format!(
+            "Glob pattern {} matched multiple files; destination must be an existing directory",
+            pattern
+        )));
+    }
+
+    Ok(matches
+        .into_iter()
+        .map(|src| {
+            let dest = if dst.is_dir() {
+                dst.join(src.file_name().expect("glob match always has a file name"))
+            } else {
+                dst.to_path_buf()
+            };
+            (src, dest)
+        })
+        .collect())
+}
+
+/// Expand any glob-pattern sources in `file_pairs`, leaving literal paths
+/// untouched, ready to hand to `bindings::batch_copy`.
+fn expand_glob_pairs<P: AsRef<Path>, Q: AsRef<Path>>(
+    file_pairs: &[(P, Q)],
+) -> Result<Vec<(PathBuf, PathBuf)>> {
+    let mut pairs = Vec::new();
+
+    for (src, dst) in file_pairs {
+        let src_path = src.as_ref();
+        let dst_path = dst.as_ref();
+
+        if is_glob_pattern(src_path) {
+            let pattern = src_path.to_string_lossy().into_owned();
+            pairs.extend(expand_glob_pair(&pattern, dst_path)?);
+        } else {
+            pairs.push((src_path.to_path_buf(), dst_path.to_path_buf()));
+        }
+    }
+
+    Ok(pairs)
+}
 
 /// EACopy class for file copy operations
 pub struct EACopy {
@@ -47,6 +115,13 @@ impl EACopy {
             )));
         }
 
+        if self.config.dry_run {
+            if !self.report_dry_run(src_path, dst_path) {
+                return Err(Error::Cancelled(dst_path.to_path_buf()));
+            }
+            return Ok(());
+        }
+
         // Create parent directory if it doesn't exist
         if let Some(parent) = dst_path.parent() {
             if !parent.exists() {
@@ -55,7 +130,13 @@ impl EACopy {
         }
 
         // Copy the file
-        bindings::copy_file(src_path, dst_path, false)?;
+        bindings::copy_file_with_strategy(
+            src_path,
+            dst_path,
+            false,
+            self.config.copy_strategy,
+            self.config.mmap_threshold,
+        )?;
 
         Ok(())
     }
@@ -92,6 +173,13 @@ impl EACopy {
             dst_path.to_path_buf()
         };
 
+        if self.config.dry_run {
+            if !self.report_dry_run(src_path, &dst_path) {
+                return Err(Error::Cancelled(dst_path.clone()));
+            }
+            return Ok(());
+        }
+
         // Create parent directory if it doesn't exist
         if let Some(parent) = dst_path.parent() {
             if !parent.exists() {
@@ -100,7 +188,13 @@ impl EACopy {
         }
 
         // Copy the file
-        bindings::copy_file(src_path, &dst_path, false)?;
+        bindings::copy_file_with_strategy(
+            src_path,
+            &dst_path,
+            false,
+            self.config.copy_strategy,
+            self.config.mmap_threshold,
+        )?;
 
         Ok(())
     }
@@ -137,6 +231,13 @@ impl EACopy {
             dst_path.to_path_buf()
         };
 
+        if self.config.dry_run {
+            if !self.report_dry_run(src_path, &dst_path) {
+                return Err(Error::Cancelled(dst_path.clone()));
+            }
+            return Ok(());
+        }
+
         // Create parent directory if it doesn't exist
         if let Some(parent) = dst_path.parent() {
             if !parent.exists() {
@@ -145,12 +246,19 @@ impl EACopy {
         }
 
         // Copy the file with metadata
-        bindings::copy_file(src_path, &dst_path, true)?;
+        bindings::copy_file_with_strategy(
+            src_path,
+            &dst_path,
+            true,
+            self.config.copy_strategy,
+            self.config.mmap_threshold,
+        )?;
 
         Ok(())
     }
 
-    /// Recursively copy a directory tree from src to dst
+    /// Recursively copy a directory tree from src to dst, returning
+    /// [`CopyStats`] describing how much work was actually done.
     pub fn copytree<P: AsRef<Path>, Q: AsRef<Path>>(
         &self,
         src: P,
@@ -158,7 +266,7 @@ impl EACopy {
         symlinks: bool,
         ignore_dangling_symlinks: bool,
         dirs_exist_ok: bool,
-    ) -> Result<()> {
+    ) -> Result<CopyStats> {
         let src_path = src.as_ref();
         let dst_path = dst.as_ref();
 
@@ -175,15 +283,194 @@ impl EACopy {
             )));
         }
 
-        // Copy the directory tree
-        bindings::copy_tree(
+        // Copy the directory tree, applying the configured filter callback
+        // and include/exclude globs, if any.
+        let filter = bindings::DirectoryFilter::new(
+            &self.config.include_globs,
+            &self.config.exclude_globs,
+            self.config.filter.clone(),
+        )?;
+
+        let options = bindings::ParallelOptions {
+            thread_count: self.config.thread_count,
+            error_strategy: self.config.error_strategy,
+            progress_callback: self.config.progress_callback.clone(),
+            dry_run: self.config.dry_run,
+            large_file_threshold: self.config.large_file_threshold,
+            range_chunk_size: self.config.range_chunk_size,
+        };
+
+        let stats = bindings::copy_directory_filtered_with_options(
             src_path,
             dst_path,
             symlinks,
             ignore_dangling_symlinks,
             dirs_exist_ok,
+            &filter,
+            &options,
+        )?;
+
+        Ok(stats)
+    }
+
+    /// Mirror `src` into `dst`, copying only files whose `(size, mtime)`
+    /// fingerprint differs from the last recorded run. The fingerprint is
+    /// persisted in a manifest file at the destination root (see
+    /// [`mirror_at`](Self::mirror_at) for another location), so unchanged files are skipped without
+    /// re-reading their content. When `purge` is set, destination files no
+    /// longer present in `src` are deleted.
+    pub fn mirror<P: AsRef<Path>, Q: AsRef<Path>>(
+        &self,
+        src: P,
+        dst: Q,
+        purge: bool,
+    ) -> Result<CopyStats> {
+        self.mirror_at(src, dst, purge, None)
+    }
+
+    /// Like [`mirror`](Self::mirror), but persisting the fingerprint
+    /// manifest at `manifest_path` instead of the default
+    /// `<dst>/.eacopy-manifest`.
+    pub fn mirror_at<P: AsRef<Path>, Q: AsRef<Path>>(
+        &self,
+        src: P,
+        dst: Q,
+        purge: bool,
+        manifest_path: Option<&Path>,
+    ) -> Result<CopyStats> {
+        let src_path = src.as_ref();
+        let dst_path = dst.as_ref();
+
+        if !src_path.is_dir() {
+            return Err(Error::InvalidArgument(format!(
+                "Source is not a directory: {}",
+                src_path.display()
+            )));
+        }
+
+        if !dst_path.exists() {
+            std::fs::create_dir_all(dst_path)?;
+        }
+
+        let mut manifest = match manifest_path {
+            Some(path) => crate::manifest::Manifest::load_from(path)?,
+            None => crate::manifest::Manifest::load(dst_path)?,
+        };
+        let mut stats = CopyStats::default();
+        let mut seen = HashSet::new();
+
+        self.mirror_visit(
+            src_path,
+            dst_path,
+            Path::new(""),
+            &mut manifest,
+            &mut stats,
+            &mut seen,
         )?;
 
+        if purge {
+            self.mirror_purge(dst_path, Path::new(""), &seen)?;
+        }
+
+        manifest.retain_only(seen.iter());
+        match manifest_path {
+            Some(path) => manifest.save_to(path)?,
+            None => manifest.save(dst_path)?,
+        }
+
+        Ok(stats)
+    }
+
+    fn mirror_visit(
+        &self,
+        src_root: &Path,
+        dst_root: &Path,
+        relative: &Path,
+        manifest: &mut crate::manifest::Manifest,
+        stats: &mut CopyStats,
+        seen: &mut HashSet<PathBuf>,
+    ) -> Result<()> {
+        let src_dir = src_root.join(relative);
+        let dst_dir = dst_root.join(relative);
+
+        if !dst_dir.exists() {
+            std::fs::create_dir_all(&dst_dir)?;
+        }
+        stats.dirs += 1;
+
+        for entry in std::fs::read_dir(&src_dir)? {
+            let entry = entry?;
+            let file_type = entry.file_type()?;
+            let entry_relative = relative.join(entry.file_name());
+
+            if file_type.is_dir() {
+                self.mirror_visit(src_root, dst_root, &entry_relative, manifest, stats, seen)?;
+                continue;
+            }
+
+            if !file_type.is_file() {
+                continue;
+            }
+
+            seen.insert(entry_relative.clone());
+            let src_path = entry.path();
+            let dst_path = dst_root.join(&entry_relative);
+            let metadata = entry.metadata()?;
+
+            let unchanged = dst_path.exists()
+                && manifest
+                    .get(&entry_relative)
+                    .map(|recorded| recorded.matches(&metadata))
+                    .transpose()?
+                    .unwrap_or(false);
+
+            if unchanged {
+                stats.skipped += 1;
+                continue;
+            }
+
+            let (bytes, exec_bit_changed) = bindings::copy_file_tracked(&src_path, &dst_path, true)?;
+            stats.files += 1;
+            stats.bytes_copied += bytes;
+            if exec_bit_changed {
+                stats.exec_bit_changes += 1;
+            }
+
+            let dst_metadata = dst_path.metadata()?;
+            manifest.insert(
+                entry_relative,
+                crate::manifest::ManifestEntry::from_metadata(&dst_metadata)?,
+            );
+        }
+
+        Ok(())
+    }
+
+    fn mirror_purge(&self, dst_root: &Path, relative: &Path, seen: &HashSet<PathBuf>) -> Result<()> {
+        let dst_dir = dst_root.join(relative);
+        if !dst_dir.exists() {
+            return Ok(());
+        }
+
+        for entry in std::fs::read_dir(&dst_dir)? {
+            let entry = entry?;
+            if entry.file_name() == crate::manifest::MANIFEST_FILE_NAME {
+                continue;
+            }
+
+            let entry_relative = relative.join(entry.file_name());
+            let file_type = entry.file_type()?;
+
+            if file_type.is_dir() {
+                self.mirror_purge(dst_root, &entry_relative, seen)?;
+                if std::fs::read_dir(entry.path())?.next().is_none() {
+                    let _ = std::fs::remove_dir(entry.path());
+                }
+            } else if !seen.contains(&entry_relative) {
+                std::fs::remove_file(entry.path())?;
+            }
+        }
+
         Ok(())
     }
 
@@ -216,48 +503,204 @@ impl EACopy {
         Ok(())
     }
 
-    /// Copy multiple files in batch
+    /// Like [`copy_with_server`](Self::copy_with_server), but for whole
+    /// directory trees: walks `src` once, packs every entry into a single
+    /// xz-compressed tar stream (preserving relative paths, symlinks per
+    /// `Config::follow_symlinks`, and metadata per `Config::preserve_metadata`),
+    /// and uploads that one stream to the server's archive listener
+    /// (`port + 1`) instead of one round trip per file. Falls back to
+    /// [`copy_with_server`](Self::copy_with_server)'s existing per-file walk
+    /// whenever building or uploading the archive fails, including when the
+    /// server has no archive listener, e.g. an older server or a real
+    /// EACopy server with no archive support.
+    pub fn copytree_with_server<P: AsRef<Path>, Q: AsRef<Path>>(
+        &self,
+        src: P,
+        dst: Q,
+        server_addr: &str,
+        port: u16,
+        compression_level: u32,
+    ) -> Result<()> {
+        let src_path = src.as_ref();
+        let dst_path = dst.as_ref();
+
+        if !src_path.is_dir() {
+            return Err(Error::InvalidArgument(format!(
+                "Source is not a directory: {}",
+                src_path.display()
+            )));
+        }
+
+        let archive_path = std::env::temp_dir().join(format!(
+            "eacopy-archive-{}.tar.xz",
+            std::process::id()
+        ));
+
+        let uploaded = bindings::build_tar_archive(
+            src_path,
+            &archive_path,
+            compression_level,
+            !self.config.follow_symlinks,
+            self.config.preserve_metadata,
+        )
+        .and_then(|_| {
+            bindings::send_archive_to_server(
+                &archive_path,
+                &dst_path.display().to_string(),
+                server_addr,
+                port + 1,
+            )
+        });
+
+        let _ = std::fs::remove_file(&archive_path);
+
+        match uploaded {
+            Ok(_bytes) => Ok(()),
+            Err(_) => self.copy_with_server(src_path, dst_path, server_addr, port, compression_level),
+        }
+    }
+
+    /// Copy every file matching `pattern` (e.g. `logs/*.txt`, `**/*.png`)
+    /// into `dst`. When the pattern matches more than one file, `dst` must
+    /// already be a directory.
+    pub fn copy_glob<P: AsRef<str>, Q: AsRef<Path>>(&self, pattern: P, dst: Q) -> Result<CopyStats> {
+        let pattern = pattern.as_ref();
+        let dst_path = dst.as_ref();
+
+        let pairs = expand_glob_pair(pattern, dst_path)?;
+        let pair_refs: Vec<(&Path, &Path)> =
+            pairs.iter().map(|(src, dst)| (src.as_path(), dst.as_path())).collect();
+
+        bindings::batch_copy(&pair_refs, true, self.config.dry_run)
+    }
+
+    /// Compress `src` into `dst` with an xz (LZMA2) stream, for a local
+    /// compressed-archive copy rather than a network/server transfer. Use
+    /// [`extract`](Self::extract) to reverse it. The dictionary/window size
+    /// is `Config::compression_window_bits`; a larger window (up to 26, i.e.
+    /// 64 MB) improves the ratio on large, highly redundant files at the
+    /// cost of memory.
+    ///
+    /// Returns a [`CopyStats`] with `bytes_copied` set to the uncompressed
+    /// size and `compressed_bytes` set to the bytes actually written, so
+    /// callers can compute the achieved ratio.
+    pub fn copy_compressed<P: AsRef<Path>, Q: AsRef<Path>>(&self, src: P, dst: Q) -> Result<CopyStats> {
+        let src_path = src.as_ref();
+        let dst_path = dst.as_ref();
+
+        if !src_path.is_file() {
+            return Err(Error::InvalidArgument(format!(
+                "Source is not a file: {}",
+                src_path.display()
+            )));
+        }
+
+        if self.config.dry_run {
+            if !self.report_dry_run(src_path, dst_path) {
+                return Err(Error::Cancelled(dst_path.to_path_buf()));
+            }
+            return Ok(CopyStats {
+                files: 1,
+                bytes_copied: std::fs::metadata(src_path).map(|m| m.len()).unwrap_or(0),
+                ..CopyStats::default()
+            });
+        }
+
+        if let Some(parent) = dst_path.parent() {
+            if !parent.exists() {
+                std::fs::create_dir_all(parent)?;
+            }
+        }
+
+        let (uncompressed_bytes, compressed_bytes) = bindings::compress_file(
+            src_path,
+            dst_path,
+            self.config.compression_level,
+            self.config.compression_window_bits,
+        )?;
+
+        Ok(CopyStats {
+            files: 1,
+            bytes_copied: uncompressed_bytes,
+            compressed_bytes,
+            ..CopyStats::default()
+        })
+    }
+
+    /// Decode an xz stream produced by [`copy_compressed`](Self::copy_compressed)
+    /// at `src` into `dst`.
+    pub fn extract<P: AsRef<Path>, Q: AsRef<Path>>(&self, src: P, dst: Q) -> Result<CopyStats> {
+        let src_path = src.as_ref();
+        let dst_path = dst.as_ref();
+
+        if !src_path.is_file() {
+            return Err(Error::InvalidArgument(format!(
+                "Source is not a file: {}",
+                src_path.display()
+            )));
+        }
+
+        if self.config.dry_run {
+            if !self.report_dry_run(src_path, dst_path) {
+                return Err(Error::Cancelled(dst_path.to_path_buf()));
+            }
+            return Ok(CopyStats::default());
+        }
+
+        if let Some(parent) = dst_path.parent() {
+            if !parent.exists() {
+                std::fs::create_dir_all(parent)?;
+            }
+        }
+
+        let bytes_copied = bindings::decompress_file(src_path, dst_path)?;
+
+        Ok(CopyStats {
+            files: 1,
+            bytes_copied,
+            ..CopyStats::default()
+        })
+    }
+
+    /// Copy multiple files in batch, returning aggregate [`CopyStats`].
+    /// A source that looks like a glob pattern (contains `*`, `?`, or `[`)
+    /// is expanded against the filesystem first; if it matches more than
+    /// one file, the paired destination must be an existing directory.
     pub fn batch_copy<P: AsRef<Path>, Q: AsRef<Path>>(
         &self,
         file_pairs: &[(P, Q)],
-    ) -> Result<()> {
-        // Convert to references
-        let pairs: Vec<(&Path, &Path)> = file_pairs
-            .iter()
-            .map(|(src, dst)| (src.as_ref(), dst.as_ref()))
-            .collect();
+    ) -> Result<CopyStats> {
+        let pairs = expand_glob_pairs(file_pairs)?;
+        let pair_refs: Vec<(&Path, &Path)> =
+            pairs.iter().map(|(src, dst)| (src.as_path(), dst.as_path())).collect();
 
         // Use the bindings function
-        bindings::batch_copy(&pairs, false)?;
-
-        Ok(())
+        bindings::batch_copy(&pair_refs, false, self.config.dry_run)
     }
 
-    /// Copy multiple files with metadata in batch
+    /// Copy multiple files with metadata in batch, returning aggregate
+    /// [`CopyStats`]. Source glob patterns are expanded as in [`batch_copy`].
     pub fn batch_copy2<P: AsRef<Path>, Q: AsRef<Path>>(
         &self,
         file_pairs: &[(P, Q)],
-    ) -> Result<()> {
-        // Convert to references
-        let pairs: Vec<(&Path, &Path)> = file_pairs
-            .iter()
-            .map(|(src, dst)| (src.as_ref(), dst.as_ref()))
-            .collect();
+    ) -> Result<CopyStats> {
+        let pairs = expand_glob_pairs(file_pairs)?;
+        let pair_refs: Vec<(&Path, &Path)> =
+            pairs.iter().map(|(src, dst)| (src.as_path(), dst.as_path())).collect();
 
         // Use the bindings function
-        bindings::batch_copy(&pairs, true)?;
-
-        Ok(())
+        bindings::batch_copy(&pair_refs, true, self.config.dry_run)
     }
 
-    /// Copy multiple directory trees in batch
+    /// Copy multiple directory trees in batch, returning aggregate
+    /// [`CopyStats`]
     pub fn batch_copytree<P: AsRef<Path>, Q: AsRef<Path>>(
         &self,
         dir_pairs: &[(P, Q)],
         symlinks: bool,
         ignore_dangling_symlinks: bool,
         dirs_exist_ok: bool,
-    ) -> Result<()> {
+    ) -> Result<CopyStats> {
         // Convert to references
         let pairs: Vec<(&Path, &Path)> = dir_pairs
             .iter()
@@ -265,15 +708,33 @@ impl EACopy {
             .collect();
 
         // Use the bindings function
-        bindings::batch_copy_tree(&pairs, symlinks, ignore_dangling_symlinks, dirs_exist_ok)?;
+        bindings::batch_copy_tree(
+            &pairs,
+            symlinks,
+            ignore_dangling_symlinks,
+            dirs_exist_ok,
+            self.config.dry_run,
+        )
+    }
 
-        Ok(())
+    /// Report a single-file copy that `dry_run` skipped via the configured
+    /// progress callback, as if the copy had actually happened. Returns
+    /// `false` if the callback requested cancellation.
+    fn report_dry_run(&self, src_path: &Path, dst_path: &Path) -> bool {
+        match &self.config.progress_callback {
+            Some(callback) => {
+                let bytes = std::fs::metadata(src_path).map(|m| m.len()).unwrap_or(0);
+                callback(bytes, bytes, &dst_path.display().to_string())
+            }
+            None => true,
+        }
     }
 
-    /// Set the progress callback function
+    /// Set the progress callback function. Returning `false` from `callback`
+    /// requests cancellation of the in-progress operation.
     pub fn set_progress_callback<F>(&mut self, callback: F)
     where
-        F: Fn(u64, u64, &str) + Send + Sync + 'static,
+        F: Fn(u64, u64, &str) -> bool + Send + Sync + 'static,
     {
         self.config.progress_callback = Some(Arc::new(callback));
     }
@@ -375,11 +836,37 @@ pub fn copytree<P: AsRef<Path>, Q: AsRef<Path>>(
     symlinks: bool,
     ignore_dangling_symlinks: bool,
     dirs_exist_ok: bool,
-) -> Result<()> {
+) -> Result<CopyStats> {
     let eacopy = EACopy::new();
     eacopy.copytree(src, dst, symlinks, ignore_dangling_symlinks, dirs_exist_ok)
 }
 
+/// Mirror `src` into `dst`, skipping files whose manifest fingerprint
+/// hasn't changed, and optionally purging destination files no longer
+/// present in `src`
+pub fn mirror<P: AsRef<Path>, Q: AsRef<Path>>(src: P, dst: Q, purge: bool) -> Result<CopyStats> {
+    let eacopy = EACopy::new();
+    eacopy.mirror(src, dst, purge)
+}
+
+/// Copy every file matching a glob pattern (e.g. `logs/*.txt`, `**/*.png`)
+pub fn copy_glob<P: AsRef<str>, Q: AsRef<Path>>(pattern: P, dst: Q) -> Result<CopyStats> {
+    let eacopy = EACopy::new();
+    eacopy.copy_glob(pattern, dst)
+}
+
+/// Compress a file into an xz stream, see [`EACopy::copy_compressed`]
+pub fn copy_compressed<P: AsRef<Path>, Q: AsRef<Path>>(src: P, dst: Q) -> Result<CopyStats> {
+    let eacopy = EACopy::new();
+    eacopy.copy_compressed(src, dst)
+}
+
+/// Decode an xz stream produced by [`copy_compressed`], see [`EACopy::extract`]
+pub fn extract<P: AsRef<Path>, Q: AsRef<Path>>(src: P, dst: Q) -> Result<CopyStats> {
+    let eacopy = EACopy::new();
+    eacopy.extract(src, dst)
+}
+
 /// Copy file or directory using EACopyService for acceleration
 pub fn copy_with_server<P: AsRef<Path>, Q: AsRef<Path>>(
     src: P,
@@ -392,14 +879,27 @@ pub fn copy_with_server<P: AsRef<Path>, Q: AsRef<Path>>(
     eacopy.copy_with_server(src, dst, server_addr, port, compression_level)
 }
 
+/// Copy a directory tree as a single tar archive upload. See
+/// [`EACopy::copytree_with_server`] for the exact fallback semantics.
+pub fn copytree_with_server<P: AsRef<Path>, Q: AsRef<Path>>(
+    src: P,
+    dst: Q,
+    server_addr: &str,
+    port: u16,
+    compression_level: u32,
+) -> Result<()> {
+    let eacopy = EACopy::new();
+    eacopy.copytree_with_server(src, dst, server_addr, port, compression_level)
+}
+
 /// Copy multiple files in batch
-pub fn batch_copy<P: AsRef<Path>, Q: AsRef<Path>>(file_pairs: &[(P, Q)]) -> Result<()> {
+pub fn batch_copy<P: AsRef<Path>, Q: AsRef<Path>>(file_pairs: &[(P, Q)]) -> Result<CopyStats> {
     let eacopy = EACopy::new();
     eacopy.batch_copy(file_pairs)
 }
 
 /// Copy multiple files with metadata in batch
-pub fn batch_copy2<P: AsRef<Path>, Q: AsRef<Path>>(file_pairs: &[(P, Q)]) -> Result<()> {
+pub fn batch_copy2<P: AsRef<Path>, Q: AsRef<Path>>(file_pairs: &[(P, Q)]) -> Result<CopyStats> {
     let eacopy = EACopy::new();
     eacopy.batch_copy2(file_pairs)
 }
@@ -410,7 +910,7 @@ pub fn batch_copytree<P: AsRef<Path>, Q: AsRef<Path>>(
     symlinks: bool,
     ignore_dangling_symlinks: bool,
     dirs_exist_ok: bool,
-) -> Result<()> {
+) -> Result<CopyStats> {
     let eacopy = EACopy::new();
     eacopy.batch_copytree(dir_pairs, symlinks, ignore_dangling_symlinks, dirs_exist_ok)
 }
@@ -429,3 +929,102 @@ pub fn delta_copy<P: AsRef<Path>, Q: AsRef<Path>, R: AsRef<Path>>(
     let eacopy = EACopy::new();
     eacopy.delta_copy(src, dst, reference)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_copy_preserves_content() -> Result<()> {
+        let dir = tempdir().map_err(Error::Io)?;
+        let src = dir.path().join("source.txt");
+        fs::write(&src, b"hello").map_err(Error::Io)?;
+        let dst = dir.path().join("dest.txt");
+
+        let eacopy = EACopy::with_config(Config::default());
+        eacopy.copy(&src, &dst)?;
+
+        assert_eq!(fs::read(&dst).map_err(Error::Io)?, b"hello");
+        Ok(())
+    }
+
+    #[test]
+    fn test_copytree_reports_stats() -> Result<()> {
+        let dir = tempdir().map_err(Error::Io)?;
+        let src = dir.path().join("src");
+        fs::create_dir(&src).map_err(Error::Io)?;
+        fs::write(src.join("a.txt"), b"a").map_err(Error::Io)?;
+        fs::create_dir(src.join("nested")).map_err(Error::Io)?;
+        fs::write(src.join("nested").join("b.txt"), b"bb").map_err(Error::Io)?;
+        let dst = dir.path().join("dst");
+
+        let eacopy = EACopy::with_config(Config::default());
+        let stats = eacopy.copytree(&src, &dst, false, false, false)?;
+
+        assert_eq!(stats.files, 2);
+        assert_eq!(stats.dirs, 2);
+        assert_eq!(stats.bytes_copied, 3);
+        assert!(dst.join("nested").join("b.txt").exists());
+        Ok(())
+    }
+
+    #[test]
+    fn test_dry_run_does_not_touch_filesystem() -> Result<()> {
+        let dir = tempdir().map_err(Error::Io)?;
+        let src = dir.path().join("source.txt");
+        fs::write(&src, b"hello").map_err(Error::Io)?;
+        let dst = dir.path().join("dest.txt");
+
+        let eacopy = EACopy::with_config(Config::default().with_dry_run(true));
+        eacopy.copy(&src, &dst)?;
+
+        assert!(!dst.exists());
+        Ok(())
+    }
+
+    #[test]
+    fn test_copy_glob_expands_matches_into_directory() -> Result<()> {
+        let dir = tempdir().map_err(Error::Io)?;
+        let src = dir.path().join("src");
+        fs::create_dir(&src).map_err(Error::Io)?;
+        fs::write(src.join("one.txt"), b"1").map_err(Error::Io)?;
+        fs::write(src.join("two.txt"), b"22").map_err(Error::Io)?;
+        fs::write(src.join("skip.log"), b"nope").map_err(Error::Io)?;
+        let dst = dir.path().join("dst");
+        fs::create_dir(&dst).map_err(Error::Io)?;
+
+        let eacopy = EACopy::with_config(Config::default());
+        let pattern = src.join("*.txt");
+        let stats = eacopy.copy_glob(pattern.to_string_lossy().as_ref(), &dst)?;
+
+        assert_eq!(stats.files, 2);
+        assert!(dst.join("one.txt").exists());
+        assert!(dst.join("two.txt").exists());
+        assert!(!dst.join("skip.log").exists());
+        Ok(())
+    }
+
+    #[test]
+    fn test_mirror_skips_unchanged_files_on_second_pass() -> Result<()> {
+        let dir = tempdir().map_err(Error::Io)?;
+        let src = dir.path().join("src");
+        fs::create_dir(&src).map_err(Error::Io)?;
+        fs::write(src.join("a.txt"), b"a").map_err(Error::Io)?;
+        // Back-date the source so the manifest entry recorded on the first
+        // pass isn't marked ambiguous (mtime in the same second as capture).
+        let mtime = std::time::SystemTime::now() - std::time::Duration::from_secs(5);
+        filetime::set_file_mtime(src.join("a.txt"), filetime::FileTime::from_system_time(mtime))
+            .map_err(Error::Io)?;
+        let dst = dir.path().join("dst");
+
+        let eacopy = EACopy::with_config(Config::default());
+        let first = eacopy.mirror(&src, &dst, false)?;
+        assert_eq!(first.files, 1);
+
+        let second = eacopy.mirror(&src, &dst, false)?;
+        assert_eq!(second.files, 0);
+        Ok(())
+    }
+}
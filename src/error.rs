@@ -5,7 +5,7 @@ use thiserror::Error;
 #[derive(Error, Debug)]
 pub enum Error {
     #[error("IO error: {0}")]
-    Io(#[from] std::io::Error),
+    Io(std::io::Error),
 
     #[error("FFI error: {0}")]
     Ffi(String),
@@ -70,13 +70,38 @@ pub enum Error {
     #[error("Configuration error: {0}")]
     Configuration(String),
 
+    #[error("Compression error: {0}")]
+    Compression(String),
+
     #[error("Unknown error: {0}")]
     Unknown(String),
+
+    #[error("Retry attempts exhausted: {0} (after {1} attempts)")]
+    RetryExhausted(PathBuf, u32),
+
+    #[error("Operation cancelled: {0}")]
+    Cancelled(PathBuf),
 }
 
 /// Result type for EACopy operations
 pub type Result<T> = std::result::Result<T, Error>;
 
+/// Convert a `std::io::Error` into an `Error`, special-casing
+/// `ErrorKind::Unsupported` (e.g. an unsupported `O_TMPFILE`-style flag, or a
+/// filesystem operation the target platform doesn't implement) into
+/// `Error::Unsupported` rather than the generic `Error::Io`, symmetric with
+/// how `impl From<pyo3::PyErr> for Error` below picks a specific variant
+/// instead of collapsing everything into `Error::Python`.
+impl From<std::io::Error> for Error {
+    fn from(err: std::io::Error) -> Self {
+        if err.kind() == std::io::ErrorKind::Unsupported {
+            Error::Unsupported(err.to_string())
+        } else {
+            Error::Io(err)
+        }
+    }
+}
+
 /// Convert a C error code to a Rust Error
 pub fn from_error_code(code: i32, path: Option<&PathBuf>) -> Error {
     let unknown_path = PathBuf::from("<unknown>");
@@ -116,65 +141,244 @@ pub fn from_error_code(code: i32, path: Option<&PathBuf>) -> Error {
     }
 }
 
+/// Convert a Python exception raised by a user-supplied callback (a filter
+/// or progress callback passed into `Config`) back into a typed `Error`,
+/// instead of collapsing it into a generic `Error::Python`. Unrecognized
+/// exception types still fall back to `Error::Python`.
+#[cfg(feature = "python")]
+impl From<pyo3::PyErr> for Error {
+    fn from(err: pyo3::PyErr) -> Self {
+        use pyo3::exceptions::*;
+        use pyo3::Python;
+
+        Python::with_gil(|py| {
+            let message = err.to_string();
+
+            if err.is_instance_of::<PyFileNotFoundError>(py) {
+                Error::FileNotFound(PathBuf::from(message))
+            } else if err.is_instance_of::<PyPermissionError>(py) {
+                Error::PermissionDenied(PathBuf::from(message))
+            } else if err.is_instance_of::<PyConnectionResetError>(py)
+                || err.is_instance_of::<PyConnectionAbortedError>(py)
+                || err.is_instance_of::<PyBrokenPipeError>(py)
+            {
+                Error::Network(message)
+            } else if err.is_instance_of::<PyTimeoutError>(py) {
+                Error::Timeout(message)
+            } else if err.is_instance_of::<PyInterruptedError>(py) {
+                Error::Interrupted(message)
+            } else {
+                Error::Python(message)
+            }
+        })
+    }
+}
+
 /// Convert a Python exception to a Rust Error
 #[cfg(feature = "python")]
 pub fn from_py_err(err: pyo3::PyErr) -> Error {
-    Error::Python(format!("{}", err))
+    Error::from(err)
+}
+
+/// Exception hierarchy raised by EACopy operations. `to_py_err` used to map
+/// straight onto stock PyO3 exceptions (`PyFileNotFoundError`,
+/// `PyConnectionError`, `PyRuntimeError`, ...), which left every copy
+/// failure, every server-mode failure, and every delta-copy mismatch
+/// wearing the same couple of generic types — a caller using
+/// `PyErrorStrategy::Retry` couldn't tell a retry-exhausted transfer apart
+/// from, say, a plain permissions problem without parsing the message.
+/// `EACopyError` is the common base of the subclasses below, so
+/// `except EACopyError` catches all of them and `isinstance` narrows. Invalid
+/// arguments and paths still raise `ValueError`, encoding failures
+/// `UnicodeError`, interruptions `KeyboardInterrupt`, and other Python
+/// errors `RuntimeError`.
+#[cfg(feature = "python")]
+pub mod exceptions {
+    use pyo3::create_exception;
+    use pyo3::exceptions::PyException;
+
+    create_exception!(_eacopy_binding, EACopyError, PyException, "Base class for all EACopy errors.");
+    create_exception!(_eacopy_binding, CopyError, EACopyError, "A file or directory copy operation failed.");
+    create_exception!(_eacopy_binding, RetryExhaustedError, EACopyError, "An operation failed after exhausting Config::retry_count attempts.");
+    create_exception!(_eacopy_binding, ServerConnectionError, EACopyError, "A network or EACopyService server-mode operation failed.");
+    create_exception!(_eacopy_binding, DeltaMismatchError, EACopyError, "A delta copy could not reconcile against its reference file.");
+    create_exception!(_eacopy_binding, ConfigurationError, EACopyError, "A Config value was invalid.");
+    create_exception!(_eacopy_binding, CompressionError, EACopyError, "A compress or decompress operation failed.");
+    create_exception!(_eacopy_binding, UnsupportedOperationError, EACopyError, "The requested operation isn't supported on this platform or filesystem.");
+    create_exception!(_eacopy_binding, CancelledError, EACopyError, "A progress callback returned False, aborting the in-progress operation.");
+}
+
+/// Build a `PyErr` of the given exception type and attach structured
+/// attributes to the instance (e.g. `source`/`destination` paths, retry
+/// `attempts`, `bytes_transferred`), so Python code can inspect exactly
+/// what happened instead of parsing the message string.
+#[cfg(feature = "python")]
+fn structured_err(
+    py: pyo3::Python<'_>,
+    new_err: impl FnOnce(String) -> pyo3::PyErr,
+    message: String,
+    attrs: &[(&str, &dyn pyo3::ToPyObject)],
+) -> pyo3::PyErr {
+    let err = new_err(message);
+    let value = err.value(py);
+    for (key, val) in attrs {
+        let _ = value.setattr(*key, val.to_object(py));
+    }
+    err
 }
 
 /// Convert a Rust Error to a Python exception
 #[cfg(feature = "python")]
 pub fn to_py_err(err: Error) -> pyo3::PyErr {
+    use exceptions::*;
     use pyo3::exceptions::*;
-    use pyo3::PyErr;
 
-    match err {
-        Error::FileNotFound(path) => PyFileNotFoundError::new_err(format!("File not found: {}", path.display())),
-        Error::DirectoryNotFound(path) => PyFileNotFoundError::new_err(format!("Directory not found: {}", path.display())),
-        Error::PermissionDenied(path) => PyPermissionError::new_err(format!("Permission denied: {}", path.display())),
-        Error::DestinationExists(path) => PyFileExistsError::new_err(format!("Destination already exists: {}", path.display())),
+    pyo3::Python::with_gil(|py| match err {
+        Error::FileNotFound(path) => structured_err(
+            py,
+            CopyError::new_err,
+            format!("File not found: {}", path.display()),
+            &[("source", &path.display().to_string())],
+        ),
+        Error::DirectoryNotFound(path) => structured_err(
+            py,
+            CopyError::new_err,
+            format!("Directory not found: {}", path.display()),
+            &[("source", &path.display().to_string())],
+        ),
+        Error::PermissionDenied(path) => structured_err(
+            py,
+            CopyError::new_err,
+            format!("Permission denied: {}", path.display()),
+            &[("destination", &path.display().to_string())],
+        ),
+        Error::DestinationExists(path) => structured_err(
+            py,
+            CopyError::new_err,
+            format!("Destination already exists: {}", path.display()),
+            &[("destination", &path.display().to_string())],
+        ),
         Error::InvalidArgument(msg) => PyValueError::new_err(msg),
-        Error::Network(msg) => PyConnectionError::new_err(msg),
-        Error::Timeout(msg) => PyTimeoutError::new_err(msg),
+        Error::Network(msg) => structured_err(py, ServerConnectionError::new_err, msg, &[]),
+        Error::Timeout(msg) => structured_err(py, ServerConnectionError::new_err, msg, &[]),
         Error::Encoding(msg) => PyUnicodeError::new_err(msg),
         Error::Python(msg) => PyRuntimeError::new_err(msg),
-        Error::FileTooLarge(path, size) => PyOSError::new_err(format!("File too large: {} ({} bytes)", path.display(), size)),
-        Error::DiskFull(path, needed, available) => PyOSError::new_err(format!(
-            "Disk full: {} (needed {} bytes, available {} bytes)",
-            path.display(), needed, available
-        )),
-        Error::ReadError(path, offset) => PyOSError::new_err(format!(
-            "Read error: {} (at offset {})",
-            path.display(), offset
-        )),
-        Error::WriteError(path, offset) => PyOSError::new_err(format!(
-            "Write error: {} (at offset {})",
-            path.display(), offset
-        )),
+        Error::FileTooLarge(path, size) => structured_err(
+            py,
+            CopyError::new_err,
+            format!("File too large: {} ({} bytes)", path.display(), size),
+            &[("source", &path.display().to_string()), ("size", &size)],
+        ),
+        Error::DiskFull(path, needed, available) => structured_err(
+            py,
+            CopyError::new_err,
+            format!(
+                "Disk full: {} (needed {} bytes, available {} bytes)",
+                path.display(), needed, available
+            ),
+            &[
+                ("destination", &path.display().to_string()),
+                ("bytes_needed", &needed),
+                ("bytes_available", &available),
+            ],
+        ),
+        Error::ReadError(path, offset) => structured_err(
+            py,
+            CopyError::new_err,
+            format!("Read error: {} (at offset {})", path.display(), offset),
+            &[("source", &path.display().to_string()), ("bytes_transferred", &offset)],
+        ),
+        Error::WriteError(path, offset) => structured_err(
+            py,
+            CopyError::new_err,
+            format!("Write error: {} (at offset {})", path.display(), offset),
+            &[("destination", &path.display().to_string()), ("bytes_transferred", &offset)],
+        ),
         Error::Interrupted(msg) => PyKeyboardInterrupt::new_err(msg),
-        Error::Server(msg) => PyConnectionError::new_err(format!("Server error: {}", msg)),
-        Error::Client(msg) => PyConnectionError::new_err(format!("Client error: {}", msg)),
-        Error::DeltaCopy(msg) => PyRuntimeError::new_err(format!("Delta copy error: {}", msg)),
-        Error::Unsupported(msg) => PyNotImplementedError::new_err(format!("Unsupported operation: {}", msg)),
-        Error::Configuration(msg) => PyValueError::new_err(format!("Configuration error: {}", msg)),
+        Error::Server(msg) => structured_err(
+            py,
+            ServerConnectionError::new_err,
+            format!("Server error: {}", msg),
+            &[],
+        ),
+        Error::Client(msg) => structured_err(
+            py,
+            ServerConnectionError::new_err,
+            format!("Client error: {}", msg),
+            &[],
+        ),
+        Error::DeltaCopy(msg) => structured_err(py, DeltaMismatchError::new_err, msg, &[]),
+        Error::Unsupported(msg) => structured_err(py, UnsupportedOperationError::new_err, msg, &[]),
+        Error::Configuration(msg) => structured_err(py, ConfigurationError::new_err, msg, &[]),
+        Error::Compression(msg) => structured_err(py, CompressionError::new_err, msg, &[]),
+        Error::RetryExhausted(path, attempts) => structured_err(
+            py,
+            RetryExhaustedError::new_err,
+            format!("Retry attempts exhausted: {} (after {} attempts)", path.display(), attempts),
+            &[("source", &path.display().to_string()), ("attempts", &attempts)],
+        ),
         Error::Io(err) => {
             let kind = err.kind();
+            let message = format!("IO error: {}", err);
             match kind {
-                std::io::ErrorKind::NotFound => PyFileNotFoundError::new_err(format!("IO error: {}", err)),
-                std::io::ErrorKind::PermissionDenied => PyPermissionError::new_err(format!("IO error: {}", err)),
-                std::io::ErrorKind::ConnectionRefused => PyConnectionError::new_err(format!("IO error: {}", err)),
-                std::io::ErrorKind::ConnectionReset => PyConnectionError::new_err(format!("IO error: {}", err)),
-                std::io::ErrorKind::ConnectionAborted => PyConnectionError::new_err(format!("IO error: {}", err)),
-                std::io::ErrorKind::NotConnected => PyConnectionError::new_err(format!("IO error: {}", err)),
-                std::io::ErrorKind::TimedOut => PyTimeoutError::new_err(format!("IO error: {}", err)),
-                std::io::ErrorKind::Interrupted => PyKeyboardInterrupt::new_err(format!("IO error: {}", err)),
-                std::io::ErrorKind::InvalidInput => PyValueError::new_err(format!("IO error: {}", err)),
-                std::io::ErrorKind::InvalidData => PyValueError::new_err(format!("IO error: {}", err)),
-                _ => PyOSError::new_err(format!("IO error: {}", err)),
+                std::io::ErrorKind::Interrupted => PyKeyboardInterrupt::new_err(message),
+                std::io::ErrorKind::InvalidInput | std::io::ErrorKind::InvalidData => {
+                    PyValueError::new_err(message)
+                }
+                std::io::ErrorKind::Unsupported => structured_err(py, UnsupportedOperationError::new_err, message, &[]),
+                std::io::ErrorKind::ConnectionRefused
+                | std::io::ErrorKind::ConnectionReset
+                | std::io::ErrorKind::ConnectionAborted
+                | std::io::ErrorKind::NotConnected
+                | std::io::ErrorKind::TimedOut => structured_err(py, ServerConnectionError::new_err, message, &[]),
+                _ => structured_err(py, CopyError::new_err, message, &[]),
             }
         },
-        Error::Ffi(msg) => PyRuntimeError::new_err(format!("FFI error: {}", msg)),
+        Error::Cancelled(path) => structured_err(
+            py,
+            CancelledError::new_err,
+            format!("Operation cancelled: {}", path.display()),
+            &[("destination", &path.display().to_string())],
+        ),
+        Error::Ffi(msg) => structured_err(py, CopyError::new_err, format!("FFI error: {}", msg), &[]),
         Error::Path(msg) => PyValueError::new_err(format!("Path error: {}", msg)),
-        Error::Unknown(msg) => PyRuntimeError::new_err(format!("Unknown error: {}", msg)),
+        Error::Unknown(msg) => EACopyError::new_err(format!("Unknown error: {}", msg)),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unsupported_io_error_maps_to_unsupported_variant() {
+        let io_err = std::io::Error::from(std::io::ErrorKind::Unsupported);
+        match Error::from(io_err) {
+            Error::Unsupported(_) => {}
+            other => panic!("expected Error::Unsupported, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_other_io_error_maps_to_io_variant() {
+        let io_err = std::io::Error::from(std::io::ErrorKind::NotFound);
+        match Error::from(io_err) {
+            Error::Io(_) => {}
+            other => panic!("expected Error::Io, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_from_error_code_known_codes() {
+        let path = PathBuf::from("/tmp/example");
+        assert!(matches!(from_error_code(2, Some(&path)), Error::FileNotFound(_)));
+        assert!(matches!(from_error_code(5, Some(&path)), Error::PermissionDenied(_)));
+        assert!(matches!(from_error_code(10060, Some(&path)), Error::Timeout(_)));
+    }
+
+    #[test]
+    fn test_from_error_code_unknown_falls_back() {
+        let path = PathBuf::from("/tmp/example");
+        assert!(matches!(from_error_code(999_999, Some(&path)), Error::Unknown(_)));
     }
 }
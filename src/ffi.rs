@@ -5,6 +5,9 @@ use std::io;
 use std::path::Path;
 use anyhow::{Result, anyhow};
 
+use crate::config::{global_config, BackupMode, Config};
+use crate::eacopy::CopyStats;
+
 /// Copy a file from source to destination
 pub fn copy_file<P: AsRef<Path>, Q: AsRef<Path>>(source: P, destination: Q) -> Result<bool> {
     // Implementation using Rust's standard library
@@ -29,16 +32,23 @@ pub fn copy_file<P: AsRef<Path>, Q: AsRef<Path>>(source: P, destination: Q) -> R
     Ok(true)
 }
 
-/// Copy a directory from source to destination
+/// Copy a directory from source to destination, returning a [`CopyStats`]
+/// summary (files, dirs, symlinks, bytes) of what was actually copied, so
+/// the pyo3 layer can report it back to callers instead of a bare bool.
+///
+/// Honors the global `Config`'s `backup_mode` (renaming an existing
+/// destination file out of the way instead of clobbering it) and
+/// `preserve_mtime`/`preserve_permissions`/`preserve_owner` flags.
 pub fn copy_directory<P: AsRef<Path>, Q: AsRef<Path>>(
     source: P,
     destination: Q,
     recursive: bool
-) -> Result<bool> {
-    // Implementation using Rust's standard library
-    let source_path = source.as_ref();
-    let dest_path = destination.as_ref();
+) -> Result<CopyStats> {
+    let config = global_config().lock().unwrap().clone();
+    copy_directory_inner(source.as_ref(), destination.as_ref(), recursive, &config)
+}
 
+fn copy_directory_inner(source_path: &Path, dest_path: &Path, recursive: bool, config: &Config) -> Result<CopyStats> {
     // Check if source exists and is a directory
     if !source_path.exists() {
         return Err(anyhow!("Source directory does not exist: {}", source_path.display()));
@@ -53,6 +63,11 @@ pub fn copy_directory<P: AsRef<Path>, Q: AsRef<Path>>(
         fs::create_dir_all(dest_path)?;
     }
 
+    let mut stats = CopyStats {
+        dirs: 1,
+        ..CopyStats::default()
+    };
+
     // Copy all entries in the directory
     for entry in fs::read_dir(source_path)? {
         let entry = entry?;
@@ -61,13 +76,150 @@ pub fn copy_directory<P: AsRef<Path>, Q: AsRef<Path>>(
         let dest_entry_path = dest_path.join(file_name);
 
         if entry_path.is_file() {
-            fs::copy(&entry_path, &dest_entry_path)?;
+            if dest_entry_path.exists() && config.backup_mode != BackupMode::None {
+                backup_existing(&dest_entry_path, &config.backup_mode)?;
+            }
+
+            let bytes = fs::copy(&entry_path, &dest_entry_path)?;
+            stats.files += 1;
+            stats.bytes_copied += bytes;
+
+            if config.preserve_mtime || config.preserve_permissions || config.preserve_owner {
+                let metadata = fs::metadata(&entry_path)?;
+
+                if config.preserve_mtime {
+                    let mtime = filetime::FileTime::from_last_modification_time(&metadata);
+                    filetime::set_file_mtime(&dest_entry_path, mtime)?;
+                }
+
+                #[cfg(unix)]
+                if config.preserve_permissions {
+                    fs::set_permissions(&dest_entry_path, metadata.permissions())?;
+                }
+
+                #[cfg(unix)]
+                if config.preserve_owner {
+                    use std::os::unix::fs::MetadataExt;
+                    std::os::unix::fs::chown(&dest_entry_path, Some(metadata.uid()), Some(metadata.gid()))?;
+                }
+            }
         } else if entry_path.is_dir() && recursive {
-            copy_directory(&entry_path, &dest_entry_path, recursive)?;
+            let nested = copy_directory_inner(&entry_path, &dest_entry_path, recursive, config)?;
+            stats.files += nested.files;
+            stats.dirs += nested.dirs;
+            stats.symlinks += nested.symlinks;
+            stats.bytes_copied += nested.bytes_copied;
         }
     }
 
-    Ok(true)
+    Ok(stats)
+}
+
+/// Move an existing destination entry out of the way per `mode` instead of
+/// letting it be overwritten in place. A no-op for `BackupMode::None`.
+fn backup_existing(path: &Path, mode: &BackupMode) -> Result<()> {
+    match mode {
+        BackupMode::None => Ok(()),
+        BackupMode::Simple { suffix } => {
+            let mut backup_name = path.as_os_str().to_os_string();
+            backup_name.push(suffix);
+            fs::rename(path, std::path::PathBuf::from(backup_name))?;
+            Ok(())
+        }
+        BackupMode::Numbered => {
+            let file_name = path.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+            let mut n = 1u32;
+            loop {
+                let candidate = path.with_file_name(format!("{}.~{}~", file_name, n));
+                if !candidate.exists() {
+                    fs::rename(path, candidate)?;
+                    return Ok(());
+                }
+                n += 1;
+            }
+        }
+    }
+}
+
+/// Expand `pattern` (a shell-style glob such as `*.png` or `logs/**/*.txt`)
+/// against the filesystem and copy every match to `destination`.
+///
+/// A single directory match requires `recursive` (otherwise it errors);
+/// a single file match is copied straight to `destination`. When the
+/// pattern expands to more than one match, `destination` must already
+/// exist as a directory, and each match is copied into it under its own
+/// base name, mirroring how `cp *.png out/` behaves.
+pub fn copy_glob<P: AsRef<str>, Q: AsRef<Path>>(
+    pattern: P,
+    destination: Q,
+    recursive: bool,
+) -> Result<CopyStats> {
+    let pattern = pattern.as_ref();
+    let destination_path = destination.as_ref();
+
+    let matches: Vec<std::path::PathBuf> = glob::glob(pattern)
+        .map_err(|e| anyhow!("Invalid glob pattern {}: {}", pattern, e))?
+        .filter_map(|entry| entry.ok())
+        .collect();
+
+    if matches.is_empty() {
+        return Err(anyhow!("Glob pattern matched no files: {}", pattern));
+    }
+
+    if matches.len() == 1 {
+        let source_path = &matches[0];
+        return if source_path.is_dir() {
+            if !recursive {
+                return Err(anyhow!(
+                    "Glob match {} is a directory; pass recursive=true to copy it",
+                    source_path.display()
+                ));
+            }
+            copy_directory(source_path, destination_path, true)
+        } else {
+            let dest_file_path = if destination_path.is_dir() {
+                let file_name = source_path.file_name().expect("glob match always has a file name");
+                destination_path.join(file_name)
+            } else {
+                destination_path.to_path_buf()
+            };
+            let bytes = fs::copy(source_path, &dest_file_path)?;
+            Ok(CopyStats { files: 1, bytes_copied: bytes, ..CopyStats::default() })
+        };
+    }
+
+    if !destination_path.is_dir() {
+        return Err(anyhow!(
+            "Destination must be an existing directory when a glob matches multiple files: {}",
+            destination_path.display()
+        ));
+    }
+
+    let mut stats = CopyStats::default();
+    for source_path in &matches {
+        let file_name = source_path.file_name().expect("glob match always has a file name");
+        let dest_entry_path = destination_path.join(file_name);
+
+        if source_path.is_dir() {
+            if !recursive {
+                return Err(anyhow!(
+                    "Glob match {} is a directory; pass recursive=true to copy it",
+                    source_path.display()
+                ));
+            }
+            let nested = copy_directory(source_path, &dest_entry_path, true)?;
+            stats.files += nested.files;
+            stats.dirs += nested.dirs;
+            stats.symlinks += nested.symlinks;
+            stats.bytes_copied += nested.bytes_copied;
+        } else {
+            let bytes = fs::copy(source_path, &dest_entry_path)?;
+            stats.files += 1;
+            stats.bytes_copied += bytes;
+        }
+    }
+
+    Ok(stats)
 }
 
 /// Get the version of the EACopy library
@@ -132,8 +284,9 @@ mod tests {
         let dest_dir = temp_dir.path().join("dest");
 
         // Copy the directory
-        let result = copy_directory(&source_dir, &dest_dir, true)?;
-        assert!(result);
+        let stats = copy_directory(&source_dir, &dest_dir, true)?;
+        assert_eq!(stats.files, 1);
+        assert_eq!(stats.dirs, 1);
 
         // Check if the directory was copied
         assert!(dest_dir.exists());
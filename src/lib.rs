@@ -1,17 +1,28 @@
 // Rust library for EACopy bindings
 // This is the main entry point for the Rust library
 
+mod backend;
+mod bindings;
+mod config;
+mod eacopy;
+mod error;
 mod ffi;
+mod manifest;
+mod python;
+mod sync;
+mod utils;
 
 use pyo3::prelude::*;
 use pyo3::exceptions::PyRuntimeError;
+use pyo3::types::PyDict;
 
 /// Python module implementation
 #[pymodule]
-fn _eacopy_binding(_py: Python, m: &PyModule) -> PyResult<()> {
+fn _eacopy_binding(py: Python, m: &PyModule) -> PyResult<()> {
     // Add functions
     m.add_function(wrap_pyfunction!(copy_file, m)?)?;
     m.add_function(wrap_pyfunction!(copy_directory, m)?)?;
+    m.add_function(wrap_pyfunction!(copy_glob, m)?)?;
     m.add_function(wrap_pyfunction!(version, m)?)?;
 
     // Add version information
@@ -23,6 +34,10 @@ fn _eacopy_binding(_py: Python, m: &PyModule) -> PyResult<()> {
         Err(_) => m.add("__eacopy_version__", "unknown")?
     };
 
+    // Add the richer EACopy/EACopyServer/Config API alongside the plain
+    // functions above, so callers can pick the level of control they need.
+    python::init_module(py, m)?;
+
     Ok(())
 }
 
@@ -33,11 +48,41 @@ fn copy_file(source: &str, destination: &str) -> PyResult<bool> {
         .map_err(|e| PyRuntimeError::new_err(format!("Failed to copy file: {}", e)))
 }
 
-/// Copy a directory from source to destination
+/// Copy a directory from source to destination, returning a dict of
+/// `{files, dirs, symlinks, bytes}` describing what was actually copied.
+#[pyfunction]
+fn copy_directory(source: &str, destination: &str, recursive: bool) -> PyResult<PyObject> {
+    let stats = ffi::copy_directory(source, destination, recursive)
+        .map_err(|e| PyRuntimeError::new_err(format!("Failed to copy directory: {}", e)))?;
+
+    Python::with_gil(|py| {
+        let dict = PyDict::new(py);
+        dict.set_item("files", stats.files)?;
+        dict.set_item("dirs", stats.dirs)?;
+        dict.set_item("symlinks", stats.symlinks)?;
+        dict.set_item("bytes", stats.bytes_copied)?;
+
+        Ok(dict.into())
+    })
+}
+
+/// Expand a shell-style glob pattern and copy every match to `destination`,
+/// returning a dict of `{files, dirs, symlinks, bytes}` describing what was
+/// actually copied.
 #[pyfunction]
-fn copy_directory(source: &str, destination: &str, recursive: bool) -> PyResult<bool> {
-    ffi::copy_directory(source, destination, recursive)
-        .map_err(|e| PyRuntimeError::new_err(format!("Failed to copy directory: {}", e)))
+fn copy_glob(pattern: &str, destination: &str, recursive: bool) -> PyResult<PyObject> {
+    let stats = ffi::copy_glob(pattern, destination, recursive)
+        .map_err(|e| PyRuntimeError::new_err(format!("Failed to copy glob: {}", e)))?;
+
+    Python::with_gil(|py| {
+        let dict = PyDict::new(py);
+        dict.set_item("files", stats.files)?;
+        dict.set_item("dirs", stats.dirs)?;
+        dict.set_item("symlinks", stats.symlinks)?;
+        dict.set_item("bytes", stats.bytes_copied)?;
+
+        Ok(dict.into())
+    })
 }
 
 /// Get the version of the EACopy library
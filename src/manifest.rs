@@ -0,0 +1,269 @@
+// Persisted per-destination manifest used by `EACopy::mirror` to decide
+// whether a file's content likely changed without re-reading it, inspired
+// by Mercurial's dirstate-v2: a compact (size, mtime, mode) fingerprint per
+// relative path instead of a full content hash.
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::error::{Error, Result};
+
+/// Name of the manifest file written at the mirror destination root.
+pub const MANIFEST_FILE_NAME: &str = ".eacopy-manifest";
+
+/// Fingerprint of a single file: its size, modification time, and mode
+/// bits, recorded the last time `mirror` copied or confirmed it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ManifestEntry {
+    pub size: u64,
+    pub mtime_secs: i64,
+    /// Sub-second resolution of `mtime`, if the filesystem reported one.
+    pub mtime_nanos: Option<u32>,
+    pub mode: u32,
+    /// True if `mtime` fell in the same second as (or after) the wall-clock
+    /// time this fingerprint was captured. On filesystems with only
+    /// 1-second `mtime` resolution (or a close race against the writing
+    /// process's own clock), a file modified again within that same second
+    /// can leave `mtime` unchanged — the classic "racy git" problem. An
+    /// ambiguous entry is never trusted to prove a file is unchanged, so
+    /// `matches` always forces a copy for it instead of risking a false
+    /// skip.
+    pub ambiguous: bool,
+}
+
+impl ManifestEntry {
+    /// Build a fingerprint from a file's current metadata, marking it
+    /// ambiguous if its `mtime` falls in the same second as, or after, the
+    /// current wall-clock time.
+    pub fn from_metadata(metadata: &std::fs::Metadata) -> Result<Self> {
+        let modified = metadata.modified().map_err(Error::Io)?;
+        let (secs, nanos) = system_time_to_parts(modified);
+        let (now_secs, _) = system_time_to_parts(SystemTime::now());
+
+        Ok(ManifestEntry {
+            size: metadata.len(),
+            mtime_secs: secs,
+            mtime_nanos: Some(nanos),
+            mode: file_mode(metadata),
+            ambiguous: secs >= now_secs,
+        })
+    }
+
+    /// Whether this recorded fingerprint still matches `source`'s current
+    /// metadata, meaning the copy can be skipped.
+    ///
+    /// Sub-second mtime resolution often differs across filesystems (e.g.
+    /// ext4's nanoseconds vs. a 1-second-granularity network share); if
+    /// either side's sub-second field is unknown, this treats the entry as
+    /// changed rather than risk a false skip. An `ambiguous` entry is
+    /// always treated as changed, regardless of what it recorded.
+    pub fn matches(&self, source: &std::fs::Metadata) -> Result<bool> {
+        if self.ambiguous {
+            return Ok(false);
+        }
+
+        if self.size != source.len() {
+            return Ok(false);
+        }
+
+        let modified = source.modified().map_err(Error::Io)?;
+        let (secs, nanos) = system_time_to_parts(modified);
+
+        if self.mtime_secs != secs {
+            return Ok(false);
+        }
+
+        Ok(self.mtime_nanos == Some(nanos))
+    }
+}
+
+fn system_time_to_parts(time: SystemTime) -> (i64, u32) {
+    match time.duration_since(SystemTime::UNIX_EPOCH) {
+        Ok(duration) => (duration.as_secs() as i64, duration.subsec_nanos()),
+        Err(err) => {
+            let before = err.duration();
+            (-(before.as_secs() as i64), before.subsec_nanos())
+        }
+    }
+}
+
+#[cfg(unix)]
+fn file_mode(metadata: &std::fs::Metadata) -> u32 {
+    use std::os::unix::fs::PermissionsExt;
+    metadata.permissions().mode()
+}
+
+#[cfg(not(unix))]
+fn file_mode(_metadata: &std::fs::Metadata) -> u32 {
+    0
+}
+
+/// A loaded manifest: relative path -> last-known fingerprint.
+#[derive(Debug, Default)]
+pub struct Manifest {
+    entries: HashMap<PathBuf, ManifestEntry>,
+}
+
+impl Manifest {
+    /// Load the manifest from `dest_root`, or an empty one if none exists
+    /// there yet (e.g. the first mirror run).
+    pub fn load(dest_root: &Path) -> Result<Self> {
+        Self::load_from(&dest_root.join(MANIFEST_FILE_NAME))
+    }
+
+    /// Load the manifest from an explicit file path, or an empty one if it
+    /// doesn't exist yet.
+    pub fn load_from(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Manifest::default());
+        }
+
+        let file = File::open(path).map_err(Error::Io)?;
+        let reader = BufReader::new(file);
+        let mut entries = HashMap::new();
+
+        for line in reader.lines() {
+            let line = line.map_err(Error::Io)?;
+            if let Some((relative, entry)) = parse_line(&line) {
+                entries.insert(relative, entry);
+            }
+        }
+
+        Ok(Manifest { entries })
+    }
+
+    /// Persist the manifest to `dest_root`.
+    pub fn save(&self, dest_root: &Path) -> Result<()> {
+        self.save_to(&dest_root.join(MANIFEST_FILE_NAME))
+    }
+
+    /// Persist the manifest to an explicit file path.
+    pub fn save_to(&self, path: &Path) -> Result<()> {
+        let mut file = File::create(path).map_err(Error::Io)?;
+
+        for (relative, entry) in &self.entries {
+            writeln!(
+                file,
+                "{} {} {} {} {} {}",
+                entry.size,
+                entry.mtime_secs,
+                entry.mtime_nanos.map(|n| n as i64).unwrap_or(-1),
+                entry.mode,
+                entry.ambiguous as u8,
+                relative.display(),
+            )
+            .map_err(Error::Io)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn get(&self, relative: &Path) -> Option<&ManifestEntry> {
+        self.entries.get(relative)
+    }
+
+    pub fn insert(&mut self, relative: PathBuf, entry: ManifestEntry) {
+        self.entries.insert(relative, entry);
+    }
+
+    /// Drop every entry whose relative path wasn't visited in the most
+    /// recent walk, so deleted source files don't linger in the manifest.
+    pub fn retain_only<'a, I: Iterator<Item = &'a PathBuf>>(&mut self, keep: I) {
+        let keep: std::collections::HashSet<&PathBuf> = keep.collect();
+        self.entries.retain(|path, _| keep.contains(path));
+    }
+}
+
+/// Parse one manifest line:
+/// `<size> <mtime_secs> <mtime_nanos> <mode> <ambiguous> <relative_path>`.
+/// `mtime_nanos` of `-1` means "unknown sub-second resolution".
+fn parse_line(line: &str) -> Option<(PathBuf, ManifestEntry)> {
+    let mut parts = line.splitn(6, ' ');
+    let size: u64 = parts.next()?.parse().ok()?;
+    let mtime_secs: i64 = parts.next()?.parse().ok()?;
+    let mtime_nanos: i64 = parts.next()?.parse().ok()?;
+    let mode: u32 = parts.next()?.parse().ok()?;
+    let ambiguous: u8 = parts.next()?.parse().ok()?;
+    let relative = parts.next()?;
+
+    Some((
+        PathBuf::from(relative),
+        ManifestEntry {
+            size,
+            mtime_secs,
+            mtime_nanos: if mtime_nanos >= 0 {
+                Some(mtime_nanos as u32)
+            } else {
+                None
+            },
+            mode,
+            ambiguous: ambiguous != 0,
+        },
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use tempfile::tempdir;
+
+    fn entry(size: u64, mtime_secs: i64, mtime_nanos: Option<u32>, ambiguous: bool) -> ManifestEntry {
+        ManifestEntry { size, mtime_secs, mtime_nanos, mode: 0o644, ambiguous }
+    }
+
+    #[test]
+    fn test_save_and_load_round_trip() -> Result<()> {
+        let dir = tempdir().map_err(Error::Io)?;
+        let mut manifest = Manifest::default();
+        manifest.insert(PathBuf::from("a/b.txt"), entry(42, 1_700_000_000, Some(123), false));
+
+        manifest.save(dir.path())?;
+        let loaded = Manifest::load(dir.path())?;
+
+        let loaded_entry = loaded.get(Path::new("a/b.txt")).expect("entry should round-trip");
+        assert_eq!(*loaded_entry, entry(42, 1_700_000_000, Some(123), false));
+        Ok(())
+    }
+
+    #[test]
+    fn test_load_missing_manifest_is_empty() -> Result<()> {
+        let dir = tempdir().map_err(Error::Io)?;
+        let manifest = Manifest::load(dir.path())?;
+        assert!(manifest.get(Path::new("anything")).is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_ambiguous_entry_never_matches() -> Result<()> {
+        let dir = tempdir().map_err(Error::Io)?;
+        let path = dir.path().join("f.txt");
+        std::fs::write(&path, b"content").map_err(Error::Io)?;
+        let metadata = path.metadata().map_err(Error::Io)?;
+
+        let ambiguous = entry(metadata.len(), 0, None, true);
+        assert!(!ambiguous.matches(&metadata)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_matching_fingerprint_matches() -> Result<()> {
+        let dir = tempdir().map_err(Error::Io)?;
+        let path = dir.path().join("f.txt");
+        std::fs::write(&path, b"content").map_err(Error::Io)?;
+        // Back-date the file slightly so `from_metadata` doesn't mark it
+        // ambiguous (mtime at/after "now" at the moment of capture).
+        let mtime = std::time::SystemTime::now() - Duration::from_secs(5);
+        filetime::set_file_mtime(&path, filetime::FileTime::from_system_time(mtime)).map_err(Error::Io)?;
+
+        let metadata = path.metadata().map_err(Error::Io)?;
+        let recorded = ManifestEntry::from_metadata(&metadata)?;
+
+        assert!(!recorded.ambiguous);
+        assert!(recorded.matches(&metadata)?);
+        Ok(())
+    }
+}
@@ -10,7 +10,7 @@ use pyo3::exceptions::{PyFileNotFoundError, PyValueError, PyRuntimeError};
 use pyo3::PyResult;
 
 use crate::eacopy::{EACopy as RustEACopy};
-use crate::config::{Config, ErrorStrategy, LogLevel, global_config};
+use crate::config::{Config, CopyStrategy, ErrorStrategy, LogLevel, global_config};
 use crate::error::{Error, Result, to_py_err};
 
 /// Python wrapper for ErrorStrategy enum
@@ -85,6 +85,53 @@ impl From<LogLevel> for PyLogLevel {
     }
 }
 
+/// Python wrapper for CopyStrategy enum
+#[pyclass]
+#[derive(Clone)]
+pub enum PyCopyStrategy {
+    #[pyo3(name = "BUFFERED")]
+    Buffered,
+    #[pyo3(name = "MMAP")]
+    Mmap,
+}
+
+impl From<PyCopyStrategy> for CopyStrategy {
+    fn from(strategy: PyCopyStrategy) -> Self {
+        match strategy {
+            PyCopyStrategy::Buffered => CopyStrategy::Buffered,
+            PyCopyStrategy::Mmap => CopyStrategy::Mmap,
+        }
+    }
+}
+
+impl From<CopyStrategy> for PyCopyStrategy {
+    fn from(strategy: CopyStrategy) -> Self {
+        match strategy {
+            CopyStrategy::Buffered => PyCopyStrategy::Buffered,
+            CopyStrategy::Mmap => PyCopyStrategy::Mmap,
+        }
+    }
+}
+
+/// Wrap a Python callable as a `ProgressCallback`, so it can be supplied
+/// through `PyEACopy::new`'s `progress_callback` parameter or `PyConfig`,
+/// not just `PyEACopy::set_progress_callback`. Returning `False` from the
+/// Python side requests cancellation; any other return value (including
+/// `None`, the common case for a callback with no explicit `return`)
+/// continues the operation.
+fn wrap_progress_callback(callback: PyObject) -> crate::config::ProgressCallback {
+    Some(Arc::new(
+        move |copied_bytes: u64, total_bytes: u64, filename: &str| -> bool {
+            Python::with_gil(|py| {
+                match callback.call1(py, (copied_bytes, total_bytes, filename)) {
+                    Ok(result) => result.extract::<bool>(py).unwrap_or(true),
+                    Err(_) => true,
+                }
+            })
+        },
+    ))
+}
+
 /// Python wrapper for Config struct
 #[pyclass]
 #[derive(Clone)]
@@ -111,6 +158,10 @@ pub struct PyConfig {
     pub dirs_exist_ok: bool,
     #[pyo3(get, set)]
     pub progress_callback: Option<PyObject>,
+    #[pyo3(get, set)]
+    pub copy_strategy: PyCopyStrategy,
+    #[pyo3(get, set)]
+    pub mmap_threshold: u64,
 }
 
 #[pymethods]
@@ -130,6 +181,8 @@ impl PyConfig {
             follow_symlinks: config.follow_symlinks,
             dirs_exist_ok: config.dirs_exist_ok,
             progress_callback: None,
+            copy_strategy: config.copy_strategy.into(),
+            mmap_threshold: config.mmap_threshold,
         }
     }
 }
@@ -147,8 +200,12 @@ impl From<PyConfig> for Config {
         config.preserve_metadata = py_config.preserve_metadata;
         config.follow_symlinks = py_config.follow_symlinks;
         config.dirs_exist_ok = py_config.dirs_exist_ok;
+        config.copy_strategy = py_config.copy_strategy.into();
+        config.mmap_threshold = py_config.mmap_threshold;
 
-        // TODO: Handle progress callback
+        if let Some(callback) = py_config.progress_callback {
+            config.progress_callback = wrap_progress_callback(callback);
+        }
 
         config
     }
@@ -158,6 +215,11 @@ impl From<PyConfig> for Config {
 #[pyclass]
 pub struct PyEACopyServer {
     inner: RustEACopy::EACopyServer,
+    /// Accepts `copytree_with_server` archive uploads on this server's
+    /// port `+ 1`, started alongside `inner` so `copytree_with_server`
+    /// clients don't have to issue one round trip per file. `None` while
+    /// the server is stopped.
+    archive_listener: Mutex<Option<crate::bindings::ArchiveListener>>,
 }
 
 #[pymethods]
@@ -168,16 +230,31 @@ impl PyEACopyServer {
         let server = RustEACopy::create_server(port, thread_count)
             .map_err(to_py_err)?;
 
-        Ok(Self { inner: server })
+        Ok(Self {
+            inner: server,
+            archive_listener: Mutex::new(None),
+        })
     }
 
     /// Start the server
     fn start(&self) -> PyResult<()> {
-        self.inner.start().map_err(to_py_err)
+        self.inner.start().map_err(to_py_err)?;
+
+        let port = self.inner.get_port();
+        match crate::bindings::ArchiveListener::start(port + 1) {
+            Ok(listener) => *self.archive_listener.lock().unwrap() = Some(listener),
+            // The archive listener is a convenience for copytree_with_server;
+            // the server itself still works without it (callers just fall
+            // back to the per-file path), so don't fail start() over it.
+            Err(_) => *self.archive_listener.lock().unwrap() = None,
+        }
+
+        Ok(())
     }
 
     /// Stop the server
     fn stop(&self) -> PyResult<()> {
+        *self.archive_listener.lock().unwrap() = None;
         self.inner.stop().map_err(to_py_err)
     }
 
@@ -232,10 +309,21 @@ impl PyEACopyServer {
     }
 }
 
+/// Stats from the most recently completed copy/copytree/batch_* operation
+/// on a `PyEACopy` instance, returned by `get_stats`.
+#[derive(Clone, Copy, Default)]
+struct LastOperationStats {
+    files: u64,
+    skipped: u64,
+    bytes: u64,
+    elapsed_secs: f64,
+}
+
 /// Python wrapper for EACopy class
 #[pyclass]
 pub struct PyEACopy {
     inner: RustEACopy,
+    last_stats: Mutex<LastOperationStats>,
 }
 
 #[pymethods]
@@ -248,7 +336,9 @@ impl PyEACopy {
         preserve_metadata=true,
         follow_symlinks=false,
         dirs_exist_ok=false,
-        progress_callback=None
+        progress_callback=None,
+        use_mmap=false,
+        mmap_threshold=64*1024*1024
     ))]
     fn new(
         thread_count: usize,
@@ -258,6 +348,8 @@ impl PyEACopy {
         follow_symlinks: bool,
         dirs_exist_ok: bool,
         progress_callback: Option<PyObject>,
+        use_mmap: bool,
+        mmap_threshold: u64,
     ) -> Self {
         let mut config = Config::default();
         config.thread_count = thread_count;
@@ -266,42 +358,100 @@ impl PyEACopy {
         config.preserve_metadata = preserve_metadata;
         config.follow_symlinks = follow_symlinks;
         config.dirs_exist_ok = dirs_exist_ok;
+        config.copy_strategy = if use_mmap {
+            CopyStrategy::Mmap
+        } else {
+            CopyStrategy::Buffered
+        };
+        config.mmap_threshold = mmap_threshold;
 
-        // TODO: Handle progress callback
+        if let Some(callback) = progress_callback {
+            config.progress_callback = wrap_progress_callback(callback);
+        }
 
         PyEACopy {
             inner: RustEACopy::with_config(config),
+            last_stats: Mutex::new(LastOperationStats::default()),
         }
     }
 
-    /// Copy file content from src to dst
+    /// Copy file content from src to dst. Files at or above the configured
+    /// `mmap_threshold` use the memory-mapped copy path when `use_mmap` was
+    /// set, falling back to the buffered path if mapping fails.
     fn copyfile(&self, src: &str, dst: &str) -> PyResult<()> {
-        self.inner.copyfile(src, dst).map_err(to_py_err)
+        let start = std::time::Instant::now();
+        self.inner.copyfile(src, dst).map_err(to_py_err)?;
+        let bytes = std::fs::metadata(dst).map(|m| m.len()).unwrap_or(0);
+        self.record_stats(1, 0, bytes, start.elapsed());
+        Ok(())
     }
 
     /// Copy a file from src to dst, preserving file content but not metadata
     fn copy(&self, src: &str, dst: &str) -> PyResult<()> {
-        self.inner.copy(src, dst).map_err(to_py_err)
+        let start = std::time::Instant::now();
+        self.inner.copy(src, dst).map_err(to_py_err)?;
+        let bytes = std::fs::metadata(dst).map(|m| m.len()).unwrap_or(0);
+        self.record_stats(1, 0, bytes, start.elapsed());
+        Ok(())
     }
 
     /// Copy a file from src to dst, preserving file content and metadata
     fn copy2(&self, src: &str, dst: &str) -> PyResult<()> {
-        self.inner.copy2(src, dst).map_err(to_py_err)
+        let start = std::time::Instant::now();
+        self.inner.copy2(src, dst).map_err(to_py_err)?;
+        let bytes = std::fs::metadata(dst).map(|m| m.len()).unwrap_or(0);
+        self.record_stats(1, 0, bytes, start.elapsed());
+        Ok(())
     }
 
-    /// Recursively copy a directory tree from src to dst
-    #[pyo3(signature = (src, dst, symlinks=false, ignore_dangling_symlinks=false, dirs_exist_ok=false))]
+    /// Recursively copy a directory tree from src to dst, returning a dict
+    /// of `{files, dirs, symlinks, bytes, skipped}` describing what was
+    /// actually copied.
+    ///
+    /// When `incremental` is set, this mirrors `src` into `dst` instead of
+    /// a plain recursive copy: a persisted manifest (at `manifest_path`, or
+    /// `<dst>/.eacopy-manifest` by default) maps each file to its
+    /// last-known `(size, mtime)`, and files whose fingerprint is unchanged
+    /// are skipped rather than re-copied.
+    #[pyo3(signature = (
+        src, dst,
+        symlinks=false,
+        ignore_dangling_symlinks=false,
+        dirs_exist_ok=false,
+        incremental=false,
+        manifest_path=None
+    ))]
     fn copytree(
         &self,
+        py: Python<'_>,
         src: &str,
         dst: &str,
         symlinks: bool,
         ignore_dangling_symlinks: bool,
         dirs_exist_ok: bool,
-    ) -> PyResult<()> {
-        self.inner
-            .copytree(src, dst, symlinks, ignore_dangling_symlinks, dirs_exist_ok)
-            .map_err(to_py_err)
+        incremental: bool,
+        manifest_path: Option<String>,
+    ) -> PyResult<PyObject> {
+        let start = std::time::Instant::now();
+        let stats = if incremental {
+            self.inner
+                .mirror_at(src, dst, false, manifest_path.as_ref().map(std::path::Path::new))
+                .map_err(to_py_err)?
+        } else {
+            self.inner
+                .copytree(src, dst, symlinks, ignore_dangling_symlinks, dirs_exist_ok)
+                .map_err(to_py_err)?
+        };
+        self.record_stats(stats.files, stats.skipped, stats.bytes_copied, start.elapsed());
+
+        let dict = PyDict::new(py);
+        dict.set_item("files", stats.files)?;
+        dict.set_item("dirs", stats.dirs)?;
+        dict.set_item("symlinks", stats.symlinks)?;
+        dict.set_item("bytes", stats.bytes_copied)?;
+        dict.set_item("skipped", stats.skipped)?;
+
+        Ok(dict.into())
     }
 
     /// Copy file or directory using EACopyService for acceleration
@@ -319,6 +469,23 @@ impl PyEACopy {
             .map_err(to_py_err)
     }
 
+    /// Copy a directory tree to a server as a single compressed tar stream,
+    /// falling back to `copy_with_server`'s per-file transfer when the
+    /// server has no archive listener.
+    #[pyo3(signature = (src, dst, server_addr, port=31337, compression_level=0))]
+    fn copytree_with_server(
+        &self,
+        src: &str,
+        dst: &str,
+        server_addr: &str,
+        port: u16,
+        compression_level: u32,
+    ) -> PyResult<()> {
+        self.inner
+            .copytree_with_server(src, dst, server_addr, port, compression_level)
+            .map_err(to_py_err)
+    }
+
     /// Copy multiple files in batch
     fn batch_copy(&self, file_pairs: &PyList) -> PyResult<()> {
         let mut pairs = Vec::new();
@@ -335,7 +502,10 @@ impl PyEACopy {
             pairs.push((src, dst));
         }
 
-        self.inner.batch_copy(&pairs).map_err(to_py_err)
+        let start = std::time::Instant::now();
+        let stats = self.inner.batch_copy(&pairs).map_err(to_py_err)?;
+        self.record_stats(stats.files, stats.skipped, stats.bytes_copied, start.elapsed());
+        Ok(())
     }
 
     /// Copy multiple files with metadata in batch
@@ -354,18 +524,36 @@ impl PyEACopy {
             pairs.push((src, dst));
         }
 
-        self.inner.batch_copy2(&pairs).map_err(to_py_err)
+        let start = std::time::Instant::now();
+        let stats = self.inner.batch_copy2(&pairs).map_err(to_py_err)?;
+        self.record_stats(stats.files, stats.skipped, stats.bytes_copied, start.elapsed());
+        Ok(())
     }
 
-    /// Copy multiple directory trees in batch
-    #[pyo3(signature = (dir_pairs, symlinks=false, ignore_dangling_symlinks=false, dirs_exist_ok=false))]
+    /// Copy multiple directory trees in batch, returning a dict of
+    /// `{files, dirs, symlinks, bytes, skipped}` describing what was
+    /// actually copied across all pairs.
+    ///
+    /// When `incremental` is set, each pair is mirrored rather than
+    /// unconditionally re-copied, using a per-pair manifest at
+    /// `<dst>/.eacopy-manifest` (see `copytree`'s `incremental` option).
+    #[pyo3(signature = (
+        dir_pairs,
+        symlinks=false,
+        ignore_dangling_symlinks=false,
+        dirs_exist_ok=false,
+        incremental=false
+    ))]
     fn batch_copytree(
         &self,
+        py: Python<'_>,
         dir_pairs: &PyList,
         symlinks: bool,
         ignore_dangling_symlinks: bool,
         dirs_exist_ok: bool,
-    ) -> PyResult<()> {
+        incremental: bool,
+    ) -> PyResult<PyObject> {
+        let start = std::time::Instant::now();
         let mut pairs = Vec::new();
 
         for item in dir_pairs.iter() {
@@ -380,27 +568,41 @@ impl PyEACopy {
             pairs.push((src, dst));
         }
 
-        self.inner
-            .batch_copytree(&pairs, symlinks, ignore_dangling_symlinks, dirs_exist_ok)
-            .map_err(to_py_err)
-    }
+        let stats = if incremental {
+            let mut combined = crate::bindings::CopyStats::default();
+            for (src, dst) in &pairs {
+                let nested = self.inner.mirror(src, dst, false).map_err(to_py_err)?;
+                combined.files += nested.files;
+                combined.dirs += nested.dirs;
+                combined.symlinks += nested.symlinks;
+                combined.bytes_copied += nested.bytes_copied;
+                combined.skipped += nested.skipped;
+            }
+            combined
+        } else {
+            self.inner
+                .batch_copytree(&pairs, symlinks, ignore_dangling_symlinks, dirs_exist_ok)
+                .map_err(to_py_err)?
+        };
+        self.record_stats(stats.files, stats.skipped, stats.bytes_copied, start.elapsed());
 
-    /// Set the progress callback function
-    fn set_progress_callback(&mut self, callback: PyObject) -> PyResult<()> {
-        let py = callback.py();
+        let dict = PyDict::new(py);
+        dict.set_item("files", stats.files)?;
+        dict.set_item("dirs", stats.dirs)?;
+        dict.set_item("symlinks", stats.symlinks)?;
+        dict.set_item("bytes", stats.bytes_copied)?;
+        dict.set_item("skipped", stats.skipped)?;
 
-        // Create a Rust callback that calls the Python function
-        let rust_callback = move |copied_bytes: u64, total_bytes: u64, filename: &str| {
-            Python::with_gil(|py| {
-                let _ = callback.call1(
-                    py,
-                    (copied_bytes, total_bytes, filename),
-                );
-            });
-        };
+        Ok(dict.into())
+    }
 
-        // Set the callback in the Rust EACopy instance
-        self.inner.set_progress_callback(rust_callback);
+    /// Set the progress callback function. Returning `False` from `callback`
+    /// requests cancellation of the in-progress operation, surfaced as
+    /// `CancelledError`.
+    fn set_progress_callback(&mut self, callback: PyObject) -> PyResult<()> {
+        if let Some(callback) = wrap_progress_callback(callback) {
+            self.inner.set_progress_callback(move |bytes, total, path| callback(bytes, total, path));
+        }
 
         Ok(())
     }
@@ -441,7 +643,7 @@ impl PyEACopy {
         let server = self.inner.create_server(port)
             .map_err(to_py_err)?;
 
-        Ok(PyEACopyServer { inner: server })
+        Ok(PyEACopyServer { inner: server, archive_listener: Mutex::new(None) })
     }
 
     /// Perform delta copy using a reference file
@@ -449,6 +651,27 @@ impl PyEACopy {
         self.inner.delta_copy(src, dst, reference).map_err(to_py_err)
     }
 
+    /// Get stats for the most recently completed copy/copytree/batch_*
+    /// operation on this instance: `{files, skipped, bytes, elapsed_seconds,
+    /// throughput_bytes_per_sec}`, mirroring `PyEACopyServer.get_stats`.
+    fn get_stats(&self, py: Python<'_>) -> PyResult<PyObject> {
+        let stats = *self.last_stats.lock().unwrap();
+        let throughput = if stats.elapsed_secs > 0.0 {
+            stats.bytes as f64 / stats.elapsed_secs
+        } else {
+            0.0
+        };
+
+        let dict = PyDict::new(py);
+        dict.set_item("files", stats.files)?;
+        dict.set_item("skipped", stats.skipped)?;
+        dict.set_item("bytes", stats.bytes)?;
+        dict.set_item("elapsed_seconds", stats.elapsed_secs)?;
+        dict.set_item("throughput_bytes_per_sec", throughput)?;
+
+        Ok(dict.into())
+    }
+
     fn __enter__(slf: PyRef<Self>) -> PyRef<Self> {
         slf
     }
@@ -464,6 +687,18 @@ impl PyEACopy {
     }
 }
 
+impl PyEACopy {
+    /// Record stats for `get_stats` after a completed operation.
+    fn record_stats(&self, files: u64, skipped: u64, bytes: u64, elapsed: std::time::Duration) {
+        *self.last_stats.lock().unwrap() = LastOperationStats {
+            files,
+            skipped,
+            bytes,
+            elapsed_secs: elapsed.as_secs_f64(),
+        };
+    }
+}
+
 /// Copy file content from src to dst
 #[pyfunction]
 fn copyfile(src: &str, dst: &str) -> PyResult<()> {
@@ -493,6 +728,7 @@ fn copytree(
     dirs_exist_ok: bool,
 ) -> PyResult<()> {
     crate::eacopy::copytree(src, dst, symlinks, ignore_dangling_symlinks, dirs_exist_ok)
+        .map(|_stats| ())
         .map_err(to_py_err)
 }
 
@@ -510,6 +746,20 @@ fn copy_with_server(
         .map_err(to_py_err)
 }
 
+/// Copy a directory tree to a server as a single compressed tar stream
+#[pyfunction]
+#[pyo3(signature = (src, dst, server_addr, port=31337, compression_level=0))]
+fn copytree_with_server(
+    src: &str,
+    dst: &str,
+    server_addr: &str,
+    port: u16,
+    compression_level: u32,
+) -> PyResult<()> {
+    crate::eacopy::copytree_with_server(src, dst, server_addr, port, compression_level)
+        .map_err(to_py_err)
+}
+
 /// Create a new EACopy server
 #[pyfunction]
 #[pyo3(signature = (port=31337, thread_count=4))]
@@ -517,7 +767,7 @@ fn create_server(port: u16, thread_count: usize) -> PyResult<PyEACopyServer> {
     let server = RustEACopy::create_server(port, thread_count)
         .map_err(to_py_err)?;
 
-    Ok(PyEACopyServer { inner: server })
+    Ok(PyEACopyServer { inner: server, archive_listener: Mutex::new(None) })
 }
 
 /// Perform delta copy using a reference file
@@ -527,13 +777,26 @@ fn delta_copy(src: &str, dst: &str, reference: &str) -> PyResult<()> {
 }
 
 /// Initialize the Python module
-pub fn init_module(_py: Python, m: &PyModule) -> PyResult<()> {
+pub fn init_module(py: Python, m: &PyModule) -> PyResult<()> {
     // Add classes
     m.add_class::<PyEACopy>()?;
     m.add_class::<PyEACopyServer>()?;
     m.add_class::<PyConfig>()?;
     m.add_class::<PyErrorStrategy>()?;
     m.add_class::<PyLogLevel>()?;
+    m.add_class::<PyCopyStrategy>()?;
+
+    // Add the structured exception hierarchy raised by `to_py_err`
+    use crate::error::exceptions::*;
+    m.add("EACopyError", py.get_type::<EACopyError>())?;
+    m.add("CopyError", py.get_type::<CopyError>())?;
+    m.add("RetryExhaustedError", py.get_type::<RetryExhaustedError>())?;
+    m.add("ServerConnectionError", py.get_type::<ServerConnectionError>())?;
+    m.add("DeltaMismatchError", py.get_type::<DeltaMismatchError>())?;
+    m.add("ConfigurationError", py.get_type::<ConfigurationError>())?;
+    m.add("CompressionError", py.get_type::<CompressionError>())?;
+    m.add("UnsupportedOperationError", py.get_type::<UnsupportedOperationError>())?;
+    m.add("CancelledError", py.get_type::<CancelledError>())?;
 
     // Add functions
     m.add_function(wrap_pyfunction!(copyfile, m)?)?;
@@ -541,6 +804,7 @@ pub fn init_module(_py: Python, m: &PyModule) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(copy2, m)?)?;
     m.add_function(wrap_pyfunction!(copytree, m)?)?;
     m.add_function(wrap_pyfunction!(copy_with_server, m)?)?;
+    m.add_function(wrap_pyfunction!(copytree_with_server, m)?)?;
     m.add_function(wrap_pyfunction!(create_server, m)?)?;
     m.add_function(wrap_pyfunction!(delta_copy, m)?)?;
 
@@ -1,11 +1,16 @@
 use std::ffi::{CString, OsStr, OsString};
+#[cfg(windows)]
 use std::os::windows::ffi::{OsStrExt, OsStringExt};
 use std::path::{Path, PathBuf};
 use std::ptr;
 
 use crate::error::{Error, Result};
 
-/// Convert a Rust string to a wide string (UTF-16) for Windows API
+/// Convert a Rust string to a wide string (UTF-16) for Windows API.
+///
+/// Only meaningful on Windows; the EACopy FFI layer is the only caller and
+/// it is itself gated behind `#[cfg(windows)]`.
+#[cfg(windows)]
 pub fn to_wide_string(s: &str) -> Vec<u16> {
     OsStr::new(s)
         .encode_wide()
@@ -14,6 +19,7 @@ pub fn to_wide_string(s: &str) -> Vec<u16> {
 }
 
 /// Convert a wide string (UTF-16) to a Rust string
+#[cfg(windows)]
 pub fn from_wide_string(wide: &[u16]) -> Result<String> {
     let len = wide.iter().position(|&c| c == 0).unwrap_or(wide.len());
     let os_string = OsString::from_wide(&wide[..len]);
@@ -28,6 +34,7 @@ pub fn to_c_string(s: &str) -> Result<CString> {
 }
 
 /// Convert a Path to a wide string (UTF-16) for Windows API
+#[cfg(windows)]
 pub fn path_to_wide_string(path: &Path) -> Vec<u16> {
     path.as_os_str()
         .encode_wide()
@@ -36,6 +43,7 @@ pub fn path_to_wide_string(path: &Path) -> Vec<u16> {
 }
 
 /// Convert a wide string (UTF-16) to a Path
+#[cfg(windows)]
 pub fn wide_string_to_path(wide: &[u16]) -> Result<PathBuf> {
     let len = wide.iter().position(|&c| c == 0).unwrap_or(wide.len());
     let os_string = OsString::from_wide(&wide[..len]);